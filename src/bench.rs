@@ -0,0 +1,237 @@
+use std::{
+  collections::HashMap,
+  fmt, fs,
+  path::PathBuf,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  runner::catch,
+  solution::{DynSolution, Part},
+  Result,
+};
+
+pub const DEFAULT_RUNS: usize = 10;
+/// Percentage a median may grow by over the baseline before it counts as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Step {
+  Parse,
+  Part(Part),
+}
+
+impl fmt::Display for Step {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Step::Parse => write!(f, "parse"),
+      Step::Part(part) => write!(f, "part{}", part.number()),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Timing {
+  pub year: u16,
+  pub day: u8,
+  pub step: Step,
+  pub min: Duration,
+  pub median: Duration,
+  pub mean: Duration,
+}
+
+fn summarize(year: u16, day: u8, step: Step, mut samples: Vec<Duration>) -> Timing {
+  samples.sort();
+  let middle = samples.len() / 2;
+  let median = match samples.len() % 2 {
+    0 => (samples[middle - 1] + samples[middle]) / 2,
+    _ => samples[middle],
+  };
+  Timing {
+    year,
+    day,
+    step,
+    min: samples[0],
+    median,
+    mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+  }
+}
+
+/// Times parsing the input and each part `runs` times. Panics are reported as errors, as when
+/// solving.
+pub fn bench_day(
+  year: u16,
+  day: u8,
+  solution: &dyn DynSolution,
+  input: &str,
+  parts: &[Part],
+  runs: usize,
+) -> Result<Vec<Timing>> {
+  let mut parse_samples = vec![];
+  let mut part_samples: Vec<Vec<Duration>> = vec![vec![]; parts.len()];
+
+  for _ in 0..runs {
+    let start = Instant::now();
+    let parsed = catch(|| solution.parse(input))?;
+    parse_samples.push(start.elapsed());
+
+    for (&part, samples) in parts.iter().zip(part_samples.iter_mut()) {
+      let start = Instant::now();
+      catch(|| solution.part(part, &parsed))?;
+      samples.push(start.elapsed());
+    }
+  }
+
+  let mut timings = vec![summarize(year, day, Step::Parse, parse_samples)];
+  for (&part, samples) in parts.iter().zip(part_samples) {
+    timings.push(summarize(year, day, Step::Part(part), samples));
+  }
+  Ok(timings)
+}
+
+pub fn to_csv(timings: &[Timing]) -> String {
+  let mut csv = String::from("year,day,step,min_ns,median_ns,mean_ns\n");
+  for timing in timings {
+    csv.push_str(&format!(
+      "{},{},{},{},{},{}\n",
+      timing.year,
+      timing.day,
+      timing.step,
+      timing.min.as_nanos(),
+      timing.median.as_nanos(),
+      timing.mean.as_nanos()
+    ));
+  }
+  csv
+}
+
+pub fn to_json(timings: &[Timing]) -> String {
+  let objects: Vec<String> = timings
+    .iter()
+    .map(|timing| {
+      format!(
+        "  {{\"year\": {}, \"day\": {}, \"step\": \"{}\", \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}}}",
+        timing.year,
+        timing.day,
+        timing.step,
+        timing.min.as_nanos(),
+        timing.median.as_nanos(),
+        timing.mean.as_nanos()
+      )
+    })
+    .collect();
+  format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+/// Writes the summary as JSON if `path` ends in `.json` and as CSV otherwise.
+pub fn write_summary(path: &PathBuf, timings: &[Timing]) -> Result<()> {
+  let summary = match path.extension().and_then(|extension| extension.to_str()) {
+    Some("json") => to_json(timings),
+    _ => to_csv(timings),
+  };
+  Ok(fs::write(path, summary)?)
+}
+
+/// Median durations by year, day and step.
+pub type Baseline = HashMap<(u16, u8, String), Duration>;
+
+/// Reads the baseline from a CSV summary of an earlier run.
+pub fn parse_baseline(csv: &str) -> Result<Baseline> {
+  let mut baseline = HashMap::new();
+  for (i, line) in csv.lines().enumerate().skip(1) {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() != 6 {
+      return Err(format!("Baseline line {}: expected 6 fields", i + 1).into());
+    }
+    let median = Duration::from_nanos(fields[4].parse::<u64>()?);
+    let key = (
+      fields[0].parse::<u16>()?,
+      fields[1].parse::<u8>()?,
+      fields[2].to_string(),
+    );
+    baseline.insert(key, median);
+  }
+  Ok(baseline)
+}
+
+/// Change of the median compared to the baseline, in percent.
+pub fn change(timing: &Timing, baseline: &Baseline) -> Option<f64> {
+  let previous = baseline.get(&(timing.year, timing.day, timing.step.to_string()))?;
+  let previous = previous.as_secs_f64().max(f64::EPSILON);
+  Some((timing.median.as_secs_f64() - previous) / previous * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::solution::Solution;
+
+  struct Unfinished;
+
+  impl Solution for Unfinished {
+    type Input = ();
+    type PartOne = u8;
+    type PartTwo = u8;
+
+    fn parse(&self, _input: &str) -> Result<()> {
+      Ok(())
+    }
+
+    fn part_one(&self, _input: &()) -> Result<u8> {
+      Ok(1)
+    }
+
+    fn part_two(&self, _input: &()) -> Result<u8> {
+      todo!("part two")
+    }
+  }
+
+  fn timing(day: u8, step: Step, median_ns: u64) -> Timing {
+    summarize(2019, day, step, vec![Duration::from_nanos(median_ns)])
+  }
+
+  #[test]
+  fn summarizes_samples() {
+    let samples = [40, 10, 30, 20].map(Duration::from_nanos).to_vec();
+    let timing = summarize(2019, 3, Step::Part(Part::Two), samples);
+    assert_eq!(timing.min, Duration::from_nanos(10));
+    assert_eq!(timing.median, Duration::from_nanos(25));
+    assert_eq!(timing.mean, Duration::from_nanos(25));
+
+    let samples = [5, 1, 3].map(Duration::from_nanos).to_vec();
+    assert_eq!(
+      summarize(2019, 3, Step::Parse, samples).median,
+      Duration::from_nanos(3)
+    );
+  }
+
+  #[test]
+  fn reports_panics_as_errors() {
+    let timings = bench_day(2019, 1, &Unfinished, "", &[Part::One], 2).unwrap();
+    assert_eq!(timings.len(), 2);
+
+    let error = bench_day(2019, 1, &Unfinished, "", &Part::BOTH, 2).unwrap_err();
+    assert!(
+      error.to_string().ends_with("not yet implemented: part two"),
+      "{}",
+      error
+    );
+  }
+
+  #[test]
+  fn compares_with_baseline() {
+    let timings = vec![
+      timing(1, Step::Parse, 100),
+      timing(1, Step::Part(Part::One), 150),
+    ];
+    let baseline = parse_baseline(&to_csv(&timings)).unwrap();
+    assert_eq!(change(&timings[1], &baseline), Some(0.0));
+
+    let slower = timing(1, Step::Part(Part::One), 300);
+    assert_eq!(change(&slower, &baseline), Some(100.0));
+    assert_eq!(change(&timing(2, Step::Parse, 1), &baseline), None);
+
+    assert!(to_json(&timings)
+      .contains("{\"year\": 2019, \"day\": 1, \"step\": \"part1\", \"min_ns\": 150"));
+  }
+}
@@ -0,0 +1,400 @@
+use std::{ops::RangeInclusive, path::PathBuf, str::FromStr, thread, time::Duration};
+
+use crate::{
+  bench,
+  inputs::{Input, Resolver},
+  solution::Part,
+  watch, years, Result,
+};
+
+pub const USAGE: &str = "\
+Usage: advent_of_code [COMMAND] [OPTIONS] [DAYS]
+       advent_of_code new DAY
+       advent_of_code decompile FILE
+
+Commands:
+  run       Print the answers (the default)
+  verify    Compare the answers with the confirmed ones in the answers file
+  bench     Time parsing and each part over several runs
+  examples  Check the answers to the puzzles' published examples
+  new       Create and register the module, input and sample input for the next day
+  watch     Re-run a single day whenever its input or alternate inputs change (not its source,
+            which needs a rebuild)
+  decompile Print an Intcode program in FILE as pseudocode
+
+DAYS is `all` (the default), a day such as `7`, a range such as `3-5` or a comma separated list
+of those, e.g. `1,3,5-7`, all of the selected year.
+
+Options:
+  -y, --year <YEAR>      Year of the days [default: the latest solved year]
+  -p, --part <1|2>       Only run the given part
+  -j, --jobs <N>         Days to solve at once for run and verify [default: available cores]
+  -f, --format <FORMAT>  Print results as `text` (the default) or `json` for run, verify and bench
+  -i, --input <PATH>     Read the input from PATH, or stdin if PATH is `-` (single day only)
+  -d, --input-dir <DIR>  Directory holding YEAR/day_N.txt [default: $AOC_INPUT_DIR or src/input]
+  -u, --user <NAME>      Use the inputs in DIR/NAME/YEAR [default: $AOC_USER]
+  -n, --name <NAME>      Use the alternate inputs day_N.NAME.txt, e.g. `sample`
+  -a, --answers <PATH>   Answers file for verify [default: answers.txt next to the inputs]
+  -r, --runs <N>         Runs per day for bench [default: 10]
+  -o, --output <PATH>    Write the bench summary to PATH, as JSON if it ends in .json or CSV
+  -b, --baseline <PATH>  Compare bench medians with a CSV summary from an earlier run
+  -t, --threshold <PCT>  Slowdown over the baseline that fails bench [default: 10]
+  -w, --interval <MS>    How often watch checks for changes [default: 500]
+  -h, --help             Print this message";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+  Run,
+  Verify,
+  Bench,
+  Examples,
+  New,
+  Watch,
+  Decompile,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+  Text,
+  Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+  pub runs: usize,
+  pub output: Option<PathBuf>,
+  pub baseline: Option<PathBuf>,
+  /// Percentage a median may grow by over the baseline.
+  pub threshold: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+  pub command: Command,
+  pub year: u16,
+  pub days: Vec<u8>,
+  /// `None` runs both parts.
+  pub part: Option<Part>,
+  pub format: Format,
+  pub jobs: usize,
+  /// Overrides `inputs` for a single day.
+  pub input: Option<Input>,
+  pub inputs: Resolver,
+  /// `None` uses the answers file next to the inputs.
+  pub answers: Option<PathBuf>,
+  pub bench: BenchOptions,
+  pub interval: Duration,
+  /// The day to create with `new`.
+  pub new_day: Option<u8>,
+  /// The Intcode program to print with `decompile`.
+  pub program: Option<PathBuf>,
+  pub help: bool,
+}
+
+fn parse_year(year: &str) -> Result<u16> {
+  let number = year
+    .parse::<u16>()
+    .map_err(|_| format!("Invalid year: {}", year))?;
+  if years::days(number).is_none() {
+    let solved: Vec<String> = years::YEARS.iter().map(|year| year.to_string()).collect();
+    return Err(
+      format!(
+        "Year {} has no solved days, expected {}",
+        number,
+        solved.join(", ")
+      )
+      .into(),
+    );
+  }
+  Ok(number)
+}
+
+fn parse_day(day: &str, solved: &RangeInclusive<u8>) -> Result<u8> {
+  let number = day
+    .parse::<u8>()
+    .map_err(|_| format!("Invalid day: {}", day))?;
+  if !solved.contains(&number) {
+    return Err(
+      format!(
+        "Day {} is not solved, expected {}-{}",
+        number,
+        solved.start(),
+        solved.end()
+      )
+      .into(),
+    );
+  }
+  Ok(number)
+}
+
+/// Parses a selection of the days solved for a year.
+fn parse_days(selection: &str, solved: RangeInclusive<u8>) -> Result<Vec<u8>> {
+  if selection == "all" {
+    return Ok(solved.collect());
+  }
+
+  let mut selected: Vec<u8> = vec![];
+  for item in selection.split(',') {
+    match item.split_once('-') {
+      Some((first, last)) => {
+        let (first, last) = (parse_day(first, &solved)?, parse_day(last, &solved)?);
+        if first > last {
+          return Err(format!("Invalid day range: {}", item).into());
+        }
+        selected.extend(first..=last);
+      }
+      None => selected.push(parse_day(item, &solved)?),
+    }
+  }
+
+  selected.sort();
+  selected.dedup();
+  Ok(selected)
+}
+
+fn parse_part(part: &str) -> Result<Part> {
+  match part {
+    "1" => Ok(Part::One),
+    "2" => Ok(Part::Two),
+    _ => Err(format!("Invalid part: {}, expected 1 or 2", part).into()),
+  }
+}
+
+fn parse_format(format: &str) -> Result<Format> {
+  match format {
+    "text" => Ok(Format::Text),
+    "json" => Ok(Format::Json),
+    _ => Err(format!("Invalid format: {}, expected text or json", format).into()),
+  }
+}
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> Result<T> {
+  value
+    .parse::<T>()
+    .map_err(|_| format!("Invalid value for {}: {}", name, value).into())
+}
+
+impl Options {
+  /// Parses the arguments following the program name.
+  pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options> {
+    let mut command: Option<Command> = None;
+    let mut year = years::LATEST;
+    // Parsed once the year is known
+    let mut days: Option<String> = None;
+    let mut part: Option<Part> = None;
+    let mut format = Format::Text;
+    let mut jobs = thread::available_parallelism().map_or(1, |jobs| jobs.get());
+    let mut input: Option<Input> = None;
+    let mut input_dir: Option<PathBuf> = None;
+    let mut user: Option<String> = None;
+    let mut name: Option<String> = None;
+    let mut answers: Option<PathBuf> = None;
+    let mut bench = BenchOptions {
+      runs: bench::DEFAULT_RUNS,
+      output: None,
+      baseline: None,
+      threshold: bench::DEFAULT_THRESHOLD,
+    };
+    let mut interval = Duration::from_millis(watch::DEFAULT_INTERVAL_MS);
+    let mut new_day: Option<u8> = None;
+    let mut program: Option<PathBuf> = None;
+    let mut help = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+      let mut value = |name: &str| {
+        args
+          .next()
+          .ok_or_else(|| format!("Missing value for {}", name))
+      };
+      match arg.as_str() {
+        "-h" | "--help" => help = true,
+        "-y" | "--year" => year = parse_year(&value(&arg)?)?,
+        "-p" | "--part" => part = Some(parse_part(&value(&arg)?)?),
+        "-f" | "--format" => format = parse_format(&value(&arg)?)?,
+        "-j" | "--jobs" => jobs = parse_number(&arg, &value(&arg)?)?,
+        "-w" | "--interval" => interval = Duration::from_millis(parse_number(&arg, &value(&arg)?)?),
+        "-i" | "--input" => input = Some(Input::from_arg(&value(&arg)?)),
+        "-d" | "--input-dir" => input_dir = Some(PathBuf::from(value(&arg)?)),
+        "-u" | "--user" => user = Some(value(&arg)?),
+        "-n" | "--name" => name = Some(value(&arg)?),
+        "-a" | "--answers" => answers = Some(PathBuf::from(value(&arg)?)),
+        "-r" | "--runs" => bench.runs = parse_number(&arg, &value(&arg)?)?,
+        "-o" | "--output" => bench.output = Some(PathBuf::from(value(&arg)?)),
+        "-b" | "--baseline" => bench.baseline = Some(PathBuf::from(value(&arg)?)),
+        "-t" | "--threshold" => bench.threshold = parse_number(&arg, &value(&arg)?)?,
+        _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg).into()),
+        "run" if command.is_none() && days.is_none() => command = Some(Command::Run),
+        "verify" if command.is_none() && days.is_none() => command = Some(Command::Verify),
+        "bench" if command.is_none() && days.is_none() => command = Some(Command::Bench),
+        "examples" if command.is_none() && days.is_none() => command = Some(Command::Examples),
+        "new" if command.is_none() && days.is_none() => command = Some(Command::New),
+        "watch" if command.is_none() && days.is_none() => command = Some(Command::Watch),
+        "decompile" if command.is_none() && days.is_none() => command = Some(Command::Decompile),
+        // The day to create isn't solved yet, so it can't be parsed like a day selection
+        _ if command == Some(Command::New) && new_day.is_none() => {
+          new_day = Some(parse_number("new", &arg)?)
+        }
+        _ if command == Some(Command::Decompile) && program.is_none() => {
+          program = Some(PathBuf::from(arg))
+        }
+        _ if days.is_some() || new_day.is_some() || program.is_some() => {
+          return Err(format!("Unexpected argument: {}", arg).into())
+        }
+        _ => days = Some(arg),
+      }
+    }
+
+    if command == Some(Command::New) && new_day.is_none() {
+      return Err("Missing the day to create".into());
+    }
+    if command == Some(Command::Decompile) && program.is_none() {
+      return Err("Missing the program to decompile".into());
+    }
+    let command = command.unwrap_or(Command::Run);
+    if format == Format::Json
+      && matches!(
+        command,
+        Command::Examples | Command::New | Command::Watch | Command::Decompile
+      )
+    {
+      return Err("--format json can only be used with run, verify and bench".into());
+    }
+    let solved = years::days(year).expect("Years are checked when parsed");
+    let days = parse_days(days.as_deref().unwrap_or("all"), solved)?;
+    if input.is_some() && days.len() != 1 {
+      return Err("--input can only be used when running a single day".into());
+    }
+    if command == Command::Watch && days.len() != 1 {
+      return Err("watch needs a single day".into());
+    }
+    if jobs == 0 {
+      return Err("--jobs must be at least 1".into());
+    }
+    if bench.runs == 0 {
+      return Err("--runs must be at least 1".into());
+    }
+
+    Ok(Options {
+      command,
+      year,
+      days,
+      part,
+      format,
+      jobs,
+      input,
+      inputs: Resolver::configure(input_dir, user, year, name),
+      answers,
+      bench,
+      interval,
+      new_day,
+      program,
+      help,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::years::year_2019::DAYS;
+
+  fn parse(args: &str) -> Result<Options> {
+    Options::parse(args.split_whitespace().map(String::from))
+  }
+
+  #[test]
+  fn selects_days_and_parts() {
+    let options = parse("").unwrap();
+    assert_eq!(options.command, Command::Run);
+    assert_eq!(options.days, DAYS.collect::<Vec<u8>>());
+    assert_eq!(options.part, None);
+
+    assert_eq!(parse("all").unwrap().days.len(), DAYS.len());
+    assert_eq!(parse("7").unwrap().days, vec![7]);
+    assert_eq!(parse("3-5").unwrap().days, vec![3, 4, 5]);
+    assert_eq!(parse("9,1,3-4,4").unwrap().days, vec![1, 3, 4, 9]);
+
+    let options = parse("10 --part 2 -i other.txt").unwrap();
+    assert_eq!(options.days, vec![10]);
+    assert_eq!(options.part, Some(Part::Two));
+    assert_eq!(options.input, Some(Input::File(PathBuf::from("other.txt"))));
+    assert_eq!(parse("3 -i -").unwrap().input, Some(Input::Stdin));
+
+    let options = parse("-d inputs -u alice -n sample").unwrap();
+    assert_eq!(
+      options.inputs.path(3),
+      PathBuf::from("inputs/alice/2019/day_3.sample.txt")
+    );
+    let options = parse("3-4 --year 2019").unwrap();
+    assert_eq!((options.year, options.days), (2019, vec![3, 4]));
+
+    let options = parse("verify 1-3 --answers mine.txt").unwrap();
+    assert_eq!(options.command, Command::Verify);
+    assert_eq!(options.days, vec![1, 2, 3]);
+    assert_eq!(options.answers, Some(PathBuf::from("mine.txt")));
+    assert_eq!(parse("examples 3").unwrap().command, Command::Examples);
+    assert_eq!(parse("new 11").unwrap().new_day, Some(11));
+    let options = parse("decompile day_9.txt").unwrap();
+    assert_eq!(options.command, Command::Decompile);
+    assert_eq!(options.program, Some(PathBuf::from("day_9.txt")));
+    assert_eq!(parse("").unwrap().format, Format::Text);
+    assert_eq!(parse("verify -f json").unwrap().format, Format::Json);
+    assert_eq!(parse("-j 3").unwrap().jobs, 3);
+    let options = parse("watch 7 -w 100").unwrap();
+    assert_eq!(options.command, Command::Watch);
+    assert_eq!(options.interval, Duration::from_millis(100));
+
+    let options = parse("bench -r 3 -o out.json -b base.csv -t 25.5").unwrap();
+    assert_eq!(options.command, Command::Bench);
+    assert_eq!(
+      options.bench,
+      BenchOptions {
+        runs: 3,
+        output: Some(PathBuf::from("out.json")),
+        baseline: Some(PathBuf::from("base.csv")),
+        threshold: 25.5,
+      }
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_arguments() {
+    let error = |args: &str| parse(args).unwrap_err().to_string();
+    assert_eq!(
+      error("0"),
+      format!("Day 0 is not solved, expected 1-{}", DAYS.end())
+    );
+    assert_eq!(error("5-3"), "Invalid day range: 5-3");
+    assert_eq!(
+      error("1 -y 2018"),
+      "Year 2018 has no solved days, expected 2019"
+    );
+    assert_eq!(error("x"), "Invalid day: x");
+    assert_eq!(error("1 --part 3"), "Invalid part: 3, expected 1 or 2");
+    assert_eq!(error("1 --part"), "Missing value for --part");
+    assert_eq!(error("--verbose"), "Unknown option: --verbose");
+    assert_eq!(error("1 2"), "Unexpected argument: 2");
+    assert_eq!(error("1 verify"), "Unexpected argument: verify");
+    assert_eq!(error("bench --runs x"), "Invalid value for --runs: x");
+    assert_eq!(error("bench --runs 0"), "--runs must be at least 1");
+    assert_eq!(error("new"), "Missing the day to create");
+    assert_eq!(error("decompile"), "Missing the program to decompile");
+    assert_eq!(error("decompile a b"), "Unexpected argument: b");
+    assert_eq!(error("--jobs 0"), "--jobs must be at least 1");
+    assert_eq!(error("watch 1-2"), "watch needs a single day");
+    assert_eq!(
+      error("-f xml"),
+      "Invalid format: xml, expected text or json"
+    );
+    assert_eq!(
+      error("examples --format json"),
+      "--format json can only be used with run, verify and bench"
+    );
+    assert_eq!(error("new 11 12"), "Unexpected argument: 12");
+    assert_eq!(
+      error("1-2 --input day.txt"),
+      "--input can only be used when running a single day"
+    );
+  }
+}
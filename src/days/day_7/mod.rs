@@ -1,8 +0,0 @@
-mod amplifier;
-mod day_7_1;
-mod day_7_2;
-
-pub fn run_day_7() {
-  day_7_1::run_day_7_1();
-  day_7_2::run_day_7_2();
-}
@@ -0,0 +1,132 @@
+use std::{fmt, io, num::ParseIntError};
+
+/// Errors returned by the library.
+#[derive(Debug)]
+pub enum Error {
+  Io(io::Error),
+  ParseInt(ParseIntError),
+  /// Anything else, such as an invalid program or a compile error.
+  Message(String),
+  /// An error with what was being done when it happened, such as the input file being read or
+  /// the line being parsed. Displayed as the whole chain, outermost context first.
+  Context {
+    context: String,
+    source: Box<Error>,
+  },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Io(error) => write!(f, "{}", error),
+      Error::ParseInt(error) => write!(f, "{}", error),
+      Error::Message(message) => write!(f, "{}", message),
+      Error::Context { context, source } => write!(f, "{}: {}", context, source),
+    }
+  }
+}
+
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Error::Io(error) => Some(error),
+      Error::ParseInt(error) => Some(error),
+      Error::Message(_) => None,
+      Error::Context { source, .. } => Some(source.as_ref()),
+    }
+  }
+}
+
+impl Error {
+  /// Wraps `error` with the 1-based line and column of the input it is about.
+  pub fn at(line: usize, column: usize, error: impl Into<Error>) -> Error {
+    Error::Context {
+      context: format!("line {}, column {}", line, column),
+      source: Box::new(error.into()),
+    }
+  }
+}
+
+/// Adds context to errors, and turns a missing value into an error.
+pub trait Context<T> {
+  fn context(self, context: impl fmt::Display) -> Result<T>;
+  fn with_context<C: fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> Context<T> for std::result::Result<T, E> {
+  fn context(self, context: impl fmt::Display) -> Result<T> {
+    self.with_context(|| context)
+  }
+
+  fn with_context<C: fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T> {
+    self.map_err(|error| Error::Context {
+      context: context().to_string(),
+      source: Box::new(error.into()),
+    })
+  }
+}
+
+impl<T> Context<T> for Option<T> {
+  fn context(self, context: impl fmt::Display) -> Result<T> {
+    self.ok_or_else(|| Error::Message(context.to_string()))
+  }
+
+  fn with_context<C: fmt::Display>(self, context: impl FnOnce() -> C) -> Result<T> {
+    self.ok_or_else(|| Error::Message(context().to_string()))
+  }
+}
+
+impl From<io::Error> for Error {
+  fn from(error: io::Error) -> Error {
+    Error::Io(error)
+  }
+}
+
+impl From<ParseIntError> for Error {
+  fn from(error: ParseIntError) -> Error {
+    Error::ParseInt(error)
+  }
+}
+
+impl From<String> for Error {
+  fn from(message: String) -> Error {
+    Error::Message(message)
+  }
+}
+
+impl From<&str> for Error {
+  fn from(message: &str) -> Error {
+    Error::Message(message.to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn chains_contexts() {
+    let error = "12x".parse::<u32>().map_err(|error| Error::at(2, 5, error));
+    let error = error.context("day_1.txt").unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "day_1.txt: line 2, column 5: invalid digit found in string"
+    );
+    let source = std::error::Error::source(&error).unwrap();
+    assert_eq!(
+      source.to_string(),
+      "line 2, column 5: invalid digit found in string"
+    );
+
+    let missing: Option<u32> = None;
+    assert_eq!(
+      missing
+        .with_context(|| "COM is missing")
+        .unwrap_err()
+        .to_string(),
+      "COM is missing"
+    );
+  }
+}
@@ -0,0 +1,182 @@
+use std::{
+  env, fmt, fs,
+  io::{self, Read},
+  path::{Path, PathBuf},
+};
+
+use crate::{Context, Result};
+
+/// Environment variable overriding the input directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+/// Environment variable selecting a per-user input set.
+pub const USER_VAR: &str = "AOC_USER";
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// The inputs shipped with the crate, found regardless of the working directory.
+pub fn default_root() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("src/input")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+  File(PathBuf),
+  Stdin,
+}
+
+impl Input {
+  /// `-` stands for stdin, anything else is a path.
+  pub fn from_arg(arg: &str) -> Input {
+    match arg {
+      "-" => Input::Stdin,
+      _ => Input::File(PathBuf::from(arg)),
+    }
+  }
+
+  pub fn read(&self) -> Result<String> {
+    match self {
+      Input::File(path) => fs::read_to_string(path).context(self),
+      Input::Stdin => {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).context(self)?;
+        Ok(input)
+      }
+    }
+  }
+}
+
+/// The file's path, or `stdin`.
+impl fmt::Display for Input {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Input::File(path) => write!(f, "{}", path.display()),
+      Input::Stdin => write!(f, "stdin"),
+    }
+  }
+}
+
+/// Finds the input for a day as `<root>[/<user>]/<year>/day_N[.<name>].txt`, so several users' and
+/// years' inputs and alternate inputs such as `day_7.sample.txt` can live side by side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resolver {
+  pub root: PathBuf,
+  pub user: Option<String>,
+  pub year: u16,
+  pub name: Option<String>,
+}
+
+impl Resolver {
+  /// Uses the given root and user, falling back to `AOC_INPUT_DIR` and `AOC_USER` and then to the
+  /// crate's own inputs.
+  pub fn configure(
+    root: Option<PathBuf>,
+    user: Option<String>,
+    year: u16,
+    name: Option<String>,
+  ) -> Resolver {
+    let non_empty = |var: &str| env::var(var).ok().filter(|value| !value.is_empty());
+    Resolver {
+      root: root
+        .or_else(|| non_empty(INPUT_DIR_VAR).map(PathBuf::from))
+        .unwrap_or_else(default_root),
+      user: user.or_else(|| non_empty(USER_VAR)),
+      year,
+      name,
+    }
+  }
+
+  pub fn directory(&self) -> PathBuf {
+    let root = match &self.user {
+      Some(user) => self.root.join(user),
+      None => self.root.clone(),
+    };
+    root.join(self.year.to_string())
+  }
+
+  pub fn path(&self, day: u8) -> PathBuf {
+    let file = match &self.name {
+      Some(name) => format!("day_{}.{}.txt", day, name),
+      None => format!("day_{}.txt", day),
+    };
+    self.directory().join(file)
+  }
+
+  pub fn answers(&self) -> PathBuf {
+    self.directory().join(ANSWERS_FILE)
+  }
+
+  /// The input file for a day, failing with the path that was tried if it doesn't exist.
+  pub fn resolve(&self, day: u8) -> Result<Input> {
+    let directory = self.directory();
+    if !directory.is_dir() {
+      return Err(
+        format!(
+          "Input directory {} does not exist, set it with --input-dir or {}",
+          directory.display(),
+          INPUT_DIR_VAR
+        )
+        .into(),
+      );
+    }
+
+    let path = self.path(day);
+    if !path.is_file() {
+      let input = match &self.name {
+        Some(name) => format!("{} input", name),
+        None => "input".to_string(),
+      };
+      return Err(format!("No {} for day {}: {} not found", input, day, path.display()).into());
+    }
+    Ok(Input::File(path))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn resolves_inputs() {
+    let resolver = Resolver::configure(Some(default_root()), None, 2019, None);
+    let year = default_root().join("2019");
+    assert_eq!(
+      resolver.resolve(7).unwrap(),
+      Input::File(year.join("day_7.txt"))
+    );
+    assert_eq!(resolver.answers(), year.join("answers.txt"));
+    assert!(resolver
+      .resolve(7)
+      .unwrap()
+      .read()
+      .unwrap()
+      .starts_with("3,8,"));
+
+    let named = Resolver {
+      name: Some("sample".to_string()),
+      ..resolver.clone()
+    };
+    assert_eq!(
+      named.resolve(7).unwrap_err().to_string(),
+      format!(
+        "No sample input for day 7: {} not found",
+        year.join("day_7.sample.txt").display()
+      )
+    );
+
+    let user = Resolver {
+      user: Some("nobody".to_string()),
+      ..resolver
+    };
+    assert_eq!(user.path(3), default_root().join("nobody/2019/day_3.txt"));
+    assert!(user
+      .resolve(3)
+      .unwrap_err()
+      .to_string()
+      .starts_with("Input directory"));
+
+    assert_eq!(Input::from_arg("-"), Input::Stdin);
+    assert_eq!(
+      Input::from_arg("a.txt"),
+      Input::File(PathBuf::from("a.txt"))
+    );
+  }
+}
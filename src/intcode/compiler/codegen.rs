@@ -0,0 +1,531 @@
+use std::collections::HashMap;
+
+use crate::Result;
+
+use super::parser::{BinaryOperator, Expression, Function, Statement, UnaryOperator};
+
+const ADD: isize = 1;
+const MULTIPLY: isize = 2;
+const SAVE_INPUT: isize = 3;
+const OUTPUT: isize = 4;
+const JUMP_IF_TRUE: isize = 5;
+const JUMP_IF_FALSE: isize = 6;
+const LESS_THAN: isize = 7;
+const EQUALS: isize = 8;
+const ADJUST_RELATIVE_BASE: isize = 9;
+const HALT: isize = 99;
+
+/// A memory value, possibly depending on a symbol (label address or frame size) that is only
+/// known once the whole program has been generated.
+#[derive(Clone, Copy)]
+enum Word {
+  Literal(isize),
+  Symbol {
+    id: usize,
+    scale: isize,
+    offset: isize,
+  },
+}
+
+#[derive(Clone, Copy)]
+enum Operand {
+  Immediate(Word),
+  Absolute(Word),
+  /// Offset from the relative base, i.e. the current stack frame.
+  Local(Word),
+}
+
+impl Operand {
+  fn mode(&self) -> isize {
+    match self {
+      Operand::Absolute(_) => 0,
+      Operand::Immediate(_) => 1,
+      Operand::Local(_) => 2,
+    }
+  }
+
+  fn word(&self) -> Word {
+    match self {
+      Operand::Immediate(word) | Operand::Absolute(word) | Operand::Local(word) => *word,
+    }
+  }
+}
+
+fn immediate(value: isize) -> Operand {
+  Operand::Immediate(Word::Literal(value))
+}
+
+fn local(offset: isize) -> Operand {
+  Operand::Local(Word::Literal(offset))
+}
+
+fn symbol(id: usize) -> Word {
+  Word::Symbol {
+    id,
+    scale: 1,
+    offset: 0,
+  }
+}
+
+/// State of the function currently being generated. Its frame is laid out as the return address,
+/// the parameters, the variables and then temporaries.
+struct Frame {
+  /// Slots of the variables declared in each enclosing block, innermost last. A block's slots are
+  /// reused once it ends.
+  scopes: Vec<HashMap<String, isize>>,
+  next_variable: isize,
+  temporaries_start: isize,
+  temporaries: isize,
+  max_temporaries: isize,
+  size: usize,
+}
+
+struct Generator {
+  code: Vec<Word>,
+  symbols: Vec<Option<isize>>,
+  functions: HashMap<String, (usize, usize)>,
+  return_value: usize,
+  frame: Frame,
+  largest_frame: isize,
+}
+
+pub struct Generated {
+  pub memory: Vec<isize>,
+  /// Size of the largest stack frame of any function.
+  pub largest_frame: usize,
+}
+
+impl Frame {
+  fn variable(&self, name: &str) -> Option<isize> {
+    self
+      .scopes
+      .iter()
+      .rev()
+      .find_map(|scope| scope.get(name).copied())
+  }
+}
+
+/// The most variables in scope at once in `statements`.
+fn max_variables(statements: &[Statement]) -> isize {
+  let mut declared = 0;
+  let mut max = 0;
+  for statement in statements {
+    let nested = match statement {
+      Statement::Let(..) => {
+        declared += 1;
+        0
+      }
+      Statement::If(_, then_branch, else_branch) => {
+        max_variables(then_branch).max(max_variables(else_branch))
+      }
+      Statement::While(_, body) => max_variables(body),
+      _ => 0,
+    };
+    max = max.max(declared + nested);
+  }
+  max
+}
+
+impl Generator {
+  fn new_symbol(&mut self) -> usize {
+    self.symbols.push(None);
+    self.symbols.len() - 1
+  }
+
+  fn place(&mut self, label: usize) {
+    self.symbols[label] = Some(self.code.len() as isize);
+  }
+
+  fn emit(&mut self, opcode: isize, operands: &[Operand]) {
+    let modes: isize = operands
+      .iter()
+      .enumerate()
+      .map(|(i, operand)| operand.mode() * 10_isize.pow(i as u32 + 2))
+      .sum();
+    self.code.push(Word::Literal(opcode + modes));
+    self.code.extend(operands.iter().map(Operand::word));
+  }
+
+  fn copy(&mut self, from: Operand, to: Operand) {
+    self.emit(ADD, &[from, immediate(0), to]);
+  }
+
+  fn jump(&mut self, label: usize) {
+    self.emit(
+      JUMP_IF_TRUE,
+      &[immediate(1), Operand::Immediate(symbol(label))],
+    );
+  }
+
+  fn temporary(&mut self) -> Operand {
+    let offset = self.frame.temporaries_start + self.frame.temporaries;
+    self.frame.temporaries += 1;
+    self.frame.max_temporaries = self.frame.max_temporaries.max(self.frame.temporaries);
+    local(offset)
+  }
+
+  /// Offset from the current frame into the frame of a function called from it.
+  fn callee_slot(&self, slot: isize) -> Operand {
+    Operand::Local(Word::Symbol {
+      id: self.frame.size,
+      scale: 1,
+      offset: slot,
+    })
+  }
+
+  fn function(&mut self, function: &Function) -> Result<()> {
+    let (label, _) = self.functions[&function.name];
+    self.place(label);
+
+    let parameters = function.parameters.len() as isize;
+    let mut variables = HashMap::new();
+    for (i, parameter) in function.parameters.iter().enumerate() {
+      if variables
+        .insert(parameter.clone(), i as isize + 1)
+        .is_some()
+      {
+        return Err(format!("{}: Duplicate parameter {}", function.position, parameter).into());
+      }
+    }
+    let size = self.new_symbol();
+    self.frame = Frame {
+      scopes: vec![variables],
+      next_variable: parameters + 1,
+      temporaries_start: parameters + 1 + max_variables(&function.body),
+      temporaries: 0,
+      max_temporaries: 0,
+      size,
+    };
+
+    self.block(&function.body)?;
+    // Falling off the end returns 0
+    self.statement(&Statement::Return(None))?;
+
+    let frame_size = self.frame.temporaries_start + self.frame.max_temporaries;
+    self.symbols[size] = Some(frame_size);
+    self.largest_frame = self.largest_frame.max(frame_size);
+    Ok(())
+  }
+
+  fn block(&mut self, statements: &[Statement]) -> Result<()> {
+    let next_variable = self.frame.next_variable;
+    self.frame.scopes.push(HashMap::new());
+    for statement in statements {
+      self.statement(statement)?;
+      self.frame.temporaries = 0;
+    }
+    self.frame.scopes.pop();
+    self.frame.next_variable = next_variable;
+    Ok(())
+  }
+
+  fn statement(&mut self, statement: &Statement) -> Result<()> {
+    match statement {
+      Statement::Let(name, value, position) => {
+        let value = self.expression(value)?;
+        if self.frame.variable(name).is_some() {
+          return Err(format!("{}: Variable {} is already declared", position, name).into());
+        }
+        let slot = self.frame.next_variable;
+        self.frame.next_variable += 1;
+        let scope = self
+          .frame
+          .scopes
+          .last_mut()
+          .expect("Statements are in a block");
+        scope.insert(name.clone(), slot);
+        self.copy(value, local(slot));
+      }
+      Statement::Assign(name, value, position) => {
+        let value = self.expression(value)?;
+        let slot = self
+          .frame
+          .variable(name)
+          .ok_or_else(|| format!("{}: Unknown variable {}", position, name))?;
+        self.copy(value, local(slot));
+      }
+      Statement::If(condition, then_branch, else_branch) => {
+        let condition = self.expression(condition)?;
+        let else_label = self.new_symbol();
+        self.emit(
+          JUMP_IF_FALSE,
+          &[condition, Operand::Immediate(symbol(else_label))],
+        );
+        self.block(then_branch)?;
+
+        if else_branch.is_empty() {
+          self.place(else_label);
+        } else {
+          let end_label = self.new_symbol();
+          self.jump(end_label);
+          self.place(else_label);
+          self.block(else_branch)?;
+          self.place(end_label);
+        }
+      }
+      Statement::While(condition, body) => {
+        let start_label = self.new_symbol();
+        let end_label = self.new_symbol();
+        self.place(start_label);
+        let condition = self.expression(condition)?;
+        self.emit(
+          JUMP_IF_FALSE,
+          &[condition, Operand::Immediate(symbol(end_label))],
+        );
+        self.block(body)?;
+        self.jump(start_label);
+        self.place(end_label);
+      }
+      Statement::Return(value) => {
+        let value = match value {
+          Some(value) => self.expression(value)?,
+          None => immediate(0),
+        };
+        self.copy(value, Operand::Absolute(symbol(self.return_value)));
+        // The caller stored the return address at the start of the frame
+        self.emit(JUMP_IF_TRUE, &[immediate(1), local(0)]);
+      }
+      Statement::Output(value) => {
+        let value = self.expression(value)?;
+        self.emit(OUTPUT, &[value]);
+      }
+      Statement::Expression(expression) => {
+        self.expression(expression)?;
+      }
+    }
+    Ok(())
+  }
+
+  fn expression(&mut self, expression: &Expression) -> Result<Operand> {
+    let mark = self.frame.temporaries;
+    let operand = match expression {
+      Expression::Number(number) => immediate(*number),
+      Expression::Variable(name, position) => local(
+        self
+          .frame
+          .variable(name)
+          .ok_or_else(|| format!("{}: Unknown variable {}", position, name))?,
+      ),
+      Expression::Input => {
+        let result = self.temporary();
+        self.emit(SAVE_INPUT, &[result]);
+        result
+      }
+      Expression::Unary(operator, operand) => {
+        let operand = self.expression(operand)?;
+        self.frame.temporaries = mark;
+        let result = self.temporary();
+        match operator {
+          UnaryOperator::Negate => self.emit(MULTIPLY, &[operand, immediate(-1), result]),
+          UnaryOperator::Not => self.emit(EQUALS, &[operand, immediate(0), result]),
+        }
+        result
+      }
+      Expression::Binary(operator, left, right) => {
+        let left = self.expression(left)?;
+        let right = self.expression(right)?;
+        self.binary(*operator, left, right, mark)
+      }
+      Expression::Call(name, arguments, position) => self.call(name, arguments, position, mark)?,
+    };
+    Ok(operand)
+  }
+
+  /// Operators that need two instructions compute into a scratch temporary first, as the result
+  /// temporary may be one of the operands.
+  fn binary(
+    &mut self,
+    operator: BinaryOperator,
+    left: Operand,
+    right: Operand,
+    mark: isize,
+  ) -> Operand {
+    let (opcode, first, second) = match operator {
+      BinaryOperator::Add => (ADD, left, right),
+      BinaryOperator::Multiply => (MULTIPLY, left, right),
+      BinaryOperator::Less => (LESS_THAN, left, right),
+      BinaryOperator::Greater => (LESS_THAN, right, left),
+      BinaryOperator::Equal => (EQUALS, left, right),
+      BinaryOperator::Subtract => {
+        let scratch = self.temporary();
+        self.emit(MULTIPLY, &[right, immediate(-1), scratch]);
+        (ADD, left, scratch)
+      }
+      BinaryOperator::LessEqual | BinaryOperator::GreaterEqual | BinaryOperator::NotEqual => {
+        // Negation of <, > and ==
+        let scratch = self.temporary();
+        match operator {
+          BinaryOperator::LessEqual => self.emit(LESS_THAN, &[right, left, scratch]),
+          BinaryOperator::GreaterEqual => self.emit(LESS_THAN, &[left, right, scratch]),
+          _ => self.emit(EQUALS, &[left, right, scratch]),
+        }
+        (EQUALS, scratch, immediate(0))
+      }
+      BinaryOperator::And | BinaryOperator::Or => {
+        // Both sides are always evaluated. Counts the false operands: none for &&, fewer than two
+        // for ||
+        let left_false = self.temporary();
+        let right_false = self.temporary();
+        self.emit(EQUALS, &[left, immediate(0), left_false]);
+        self.emit(EQUALS, &[right, immediate(0), right_false]);
+        let combine = match operator {
+          BinaryOperator::And => ADD,
+          _ => MULTIPLY,
+        };
+        self.emit(combine, &[left_false, right_false, left_false]);
+        (EQUALS, left_false, immediate(0))
+      }
+    };
+
+    self.frame.temporaries = mark;
+    let result = self.temporary();
+    self.emit(opcode, &[first, second, result]);
+    result
+  }
+
+  fn call(
+    &mut self,
+    name: &str,
+    arguments: &[Expression],
+    position: &super::Position,
+    mark: isize,
+  ) -> Result<Operand> {
+    let (label, arity) = *self
+      .functions
+      .get(name)
+      .ok_or_else(|| format!("{}: Unknown function {}", position, name))?;
+    if arguments.len() != arity {
+      return Err(
+        format!(
+          "{}: {} takes {} arguments but {} were given",
+          position,
+          name,
+          arity,
+          arguments.len()
+        )
+        .into(),
+      );
+    }
+
+    let arguments = arguments
+      .iter()
+      .map(|argument| self.expression(argument))
+      .collect::<Result<Vec<_>>>()?;
+    for (i, argument) in arguments.into_iter().enumerate() {
+      let slot = self.callee_slot(i as isize + 1);
+      self.copy(argument, slot);
+    }
+
+    // Store the return address in the callee's frame, move the relative base to it and jump.
+    // Execution resumes below, where the frame is moved back.
+    let return_label = self.new_symbol();
+    let frame_size = Word::Symbol {
+      id: self.frame.size,
+      scale: 1,
+      offset: 0,
+    };
+    let slot = self.callee_slot(0);
+    self.copy(Operand::Immediate(symbol(return_label)), slot);
+    self.emit(ADJUST_RELATIVE_BASE, &[Operand::Immediate(frame_size)]);
+    self.jump(label);
+    self.place(return_label);
+    self.emit(
+      ADJUST_RELATIVE_BASE,
+      &[Operand::Immediate(Word::Symbol {
+        id: self.frame.size,
+        scale: -1,
+        offset: 0,
+      })],
+    );
+
+    self.frame.temporaries = mark;
+    let result = self.temporary();
+    self.copy(Operand::Absolute(symbol(self.return_value)), result);
+    Ok(result)
+  }
+
+  fn resolve(&self) -> Vec<isize> {
+    self
+      .code
+      .iter()
+      .map(|word| match word {
+        Word::Literal(value) => *value,
+        Word::Symbol { id, scale, offset } => {
+          self.symbols[*id].expect("Unplaced symbol") * scale + offset
+        }
+      })
+      .collect()
+  }
+}
+
+/// Generates a program that sets up the stack after its code, calls `main` and halts.
+pub fn generate(functions: &[Function]) -> Result<Generated> {
+  let mut generator = Generator {
+    code: vec![],
+    symbols: vec![],
+    functions: HashMap::new(),
+    return_value: 0,
+    frame: Frame {
+      scopes: vec![],
+      next_variable: 0,
+      temporaries_start: 0,
+      temporaries: 0,
+      max_temporaries: 0,
+      size: 0,
+    },
+    largest_frame: 0,
+  };
+
+  for function in functions {
+    if function.name == "input" || function.name == "output" {
+      return Err(
+        format!(
+          "{}: {} is a built-in function",
+          function.position, function.name
+        )
+        .into(),
+      );
+    }
+    let label = generator.new_symbol();
+    let previous = generator
+      .functions
+      .insert(function.name.clone(), (label, function.parameters.len()));
+    if previous.is_some() {
+      return Err(
+        format!(
+          "{}: Function {} is already defined",
+          function.position, function.name
+        )
+        .into(),
+      );
+    }
+  }
+  let main = match generator.functions.get("main") {
+    Some((label, 0)) => *label,
+    Some(_) => return Err("main must not take any parameters".into()),
+    None => return Err("Missing main function".into()),
+  };
+
+  generator.return_value = generator.new_symbol();
+  let stack = generator.new_symbol();
+  let halt = generator.new_symbol();
+  generator.emit(ADJUST_RELATIVE_BASE, &[Operand::Immediate(symbol(stack))]);
+  generator.copy(Operand::Immediate(symbol(halt)), local(0));
+  generator.jump(main);
+  generator.place(halt);
+  generator.emit(HALT, &[]);
+
+  for function in functions {
+    generator.function(function)?;
+  }
+
+  let return_value = generator.return_value;
+  generator.place(return_value);
+  generator.code.push(Word::Literal(0));
+  generator.place(stack);
+
+  Ok(Generated {
+    memory: generator.resolve(),
+    largest_frame: generator.largest_frame as usize,
+  })
+}
@@ -0,0 +1,141 @@
+use crate::Result;
+
+use super::Position;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+  Number(isize),
+  Identifier(String),
+  Fn,
+  Let,
+  If,
+  Else,
+  While,
+  Return,
+  LeftParen,
+  RightParen,
+  LeftBrace,
+  RightBrace,
+  Comma,
+  Semicolon,
+  Assign,
+  Plus,
+  Minus,
+  Star,
+  Not,
+  Less,
+  LessEqual,
+  Greater,
+  GreaterEqual,
+  Equal,
+  NotEqual,
+  And,
+  Or,
+  End,
+}
+
+fn keyword_or_identifier(word: String) -> Token {
+  match word.as_str() {
+    "fn" => Token::Fn,
+    "let" => Token::Let,
+    "if" => Token::If,
+    "else" => Token::Else,
+    "while" => Token::While,
+    "return" => Token::Return,
+    _ => Token::Identifier(word),
+  }
+}
+
+/// Splits source into tokens, each paired with the position it starts at. The last token is
+/// always `Token::End`.
+pub fn tokenize(source: &str) -> Result<Vec<(Token, Position)>> {
+  let chars: Vec<char> = source.chars().collect();
+  let mut tokens = vec![];
+  let mut i = 0;
+  let mut position = Position { line: 1, column: 1 };
+
+  while i < chars.len() {
+    let c = chars[i];
+    let start = position;
+    let next = chars.get(i + 1).copied();
+
+    if c == '\n' {
+      i += 1;
+      position.line += 1;
+      position.column = 1;
+      continue;
+    }
+    if c.is_whitespace() {
+      i += 1;
+      position.column += 1;
+      continue;
+    }
+    if c == '/' && next == Some('/') {
+      while i < chars.len() && chars[i] != '\n' {
+        i += 1;
+      }
+      continue;
+    }
+
+    let mut length = 1;
+    let token = match c {
+      '0'..='9' => {
+        let digits: String = chars[i..]
+          .iter()
+          .take_while(|c| c.is_ascii_digit())
+          .collect();
+        length = digits.len();
+        let number = digits
+          .parse::<isize>()
+          .map_err(|_| format!("{}: Number {} is too large", start, digits))?;
+        Token::Number(number)
+      }
+      c if c.is_alphabetic() || c == '_' => {
+        let word: String = chars[i..]
+          .iter()
+          .take_while(|c| c.is_alphanumeric() || **c == '_')
+          .collect();
+        length = word.chars().count();
+        keyword_or_identifier(word)
+      }
+      '(' => Token::LeftParen,
+      ')' => Token::RightParen,
+      '{' => Token::LeftBrace,
+      '}' => Token::RightBrace,
+      ',' => Token::Comma,
+      ';' => Token::Semicolon,
+      '+' => Token::Plus,
+      '-' => Token::Minus,
+      '*' => Token::Star,
+      '<' | '>' | '=' | '!' if next == Some('=') => {
+        length = 2;
+        match c {
+          '<' => Token::LessEqual,
+          '>' => Token::GreaterEqual,
+          '=' => Token::Equal,
+          _ => Token::NotEqual,
+        }
+      }
+      '&' if next == Some('&') => {
+        length = 2;
+        Token::And
+      }
+      '|' if next == Some('|') => {
+        length = 2;
+        Token::Or
+      }
+      '<' => Token::Less,
+      '>' => Token::Greater,
+      '=' => Token::Assign,
+      '!' => Token::Not,
+      _ => return Err(format!("{}: Unexpected character '{}'", start, c).into()),
+    };
+
+    tokens.push((token, start));
+    i += length;
+    position.column += length;
+  }
+
+  tokens.push((Token::End, position));
+  Ok(tokens)
+}
@@ -0,0 +1,270 @@
+//! Compiler for a tiny structured language targeting Intcode.
+//!
+//! ```text
+//! fn factorial(n) {
+//!   if n < 2 { return 1; }
+//!   return n * factorial(n - 1);
+//! }
+//!
+//! fn main() {
+//!   let n = input();
+//!   while n > 0 {
+//!     output(factorial(n));
+//!     n = n - 1;
+//!   }
+//! }
+//! ```
+//!
+//! Values are integers, with `+ - *`, comparisons and `! && ||` (which evaluate both sides)
+//! returning 1 or 0. Variables are declared with `let`, last until the end of their block and can't
+//! shadow another variable in scope. Execution starts at `main`; `input()` and `output(x)` map to
+//! the Intcode I/O instructions.
+//!
+//! The relative base is the stack pointer: each call moves it to a new frame holding the return
+//! address, arguments, variables and temporaries, which is what makes recursion possible. The
+//! stack starts right after the program, so recursion is bounded by the program's memory.
+//! `CompiledProgram::run` reports a run that fails once the stack reaches the end of memory as
+//! `Stack exhausted`, which running the `Program` directly reports as a memory access outside it.
+
+use std::fmt;
+
+use crate::{Context, Result};
+
+use self::{codegen::Generated, lexer::tokenize, parser::Parser};
+
+use super::program::Program;
+
+mod codegen;
+mod lexer;
+mod parser;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+  pub line: usize,
+  pub column: usize,
+}
+
+impl fmt::Display for Position {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}", self.line, self.column)
+  }
+}
+
+fn generate(source: &str) -> Result<Generated> {
+  let functions = Parser::new(tokenize(source)?).program()?;
+  codegen::generate(&functions)
+}
+
+/// Compiles source to Intcode memory. Errors are prefixed with the `line:column` they occur at.
+pub fn compile(source: &str) -> Result<Vec<isize>> {
+  Ok(generate(source)?.memory)
+}
+
+pub fn compile_program(source: &str) -> Result<CompiledProgram> {
+  let generated = generate(source)?;
+  Ok(CompiledProgram {
+    program: Program::from(generated.memory),
+    largest_frame: generated.largest_frame,
+  })
+}
+
+/// A compiled program, which knows how far its stack may reach.
+pub struct CompiledProgram {
+  pub program: Program,
+  largest_frame: usize,
+}
+
+impl CompiledProgram {
+  /// Runs the program, failing with `Stack exhausted` if it failed with a call's frames not fitting
+  /// in memory.
+  pub fn run(&mut self, inputs: Option<Vec<isize>>) -> Result<Vec<isize>> {
+    let result = self.program.run(inputs);
+    if result.is_err() && self.stack_is_full() {
+      return result.context("Stack exhausted");
+    }
+    result
+  }
+
+  /// Whether the current frame and that of a call from it reach past the end of memory.
+  fn stack_is_full(&self) -> bool {
+    usize::try_from(self.program.relative_base)
+      .is_ok_and(|base| base + 2 * self.largest_frame > self.program.memory.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn run(source: &str, inputs: Vec<isize>) -> Vec<isize> {
+    compile_program(source).unwrap().run(Some(inputs)).unwrap()
+  }
+
+  fn error(source: &str) -> String {
+    compile(source).unwrap_err().to_string()
+  }
+
+  #[test]
+  fn recursion_uses_the_stack() {
+    let source = "
+      fn factorial(n) {
+        if n < 2 { return 1; }
+        return n * factorial(n - 1);
+      }
+
+      fn fibonacci(n) {
+        if n <= 1 { return n; }
+        return fibonacci(n - 1) + fibonacci(n - 2);
+      }
+
+      fn main() {
+        let n = input();
+        output(factorial(n));
+        output(fibonacci(n));
+      }
+    ";
+    assert_eq!(run(source, vec![10]), vec![3628800, 55]);
+  }
+
+  #[test]
+  fn loops_and_branches() {
+    let source = "
+      // Sums the inputs until a zero, then classifies the sum
+      fn main() {
+        let sum = 0;
+        let value = input();
+        while value != 0 {
+          sum = sum + value;
+          value = input();
+        }
+        output(sum);
+
+        if sum > 100 {
+          output(2);
+        } else if sum >= 10 && !(sum == 50) {
+          output(1);
+        } else {
+          output(0);
+        }
+      }
+    ";
+    assert_eq!(run(source, vec![5, 7, -2, 0]), vec![10, 1]);
+    assert_eq!(run(source, vec![50, 0]), vec![50, 0]);
+    assert_eq!(run(source, vec![99, 2, 0]), vec![101, 2]);
+  }
+
+  #[test]
+  fn operators() {
+    let source = "
+      fn main() {
+        let a = input();
+        let b = input();
+        output(a - b);
+        output(-a * 3 + b);
+        output(a < b);
+        output(a > b);
+        output(a <= b);
+        output(a >= b);
+        output(a == b);
+        output(a != b);
+        output(a || 0);
+        output(0 || 0);
+      }
+    ";
+    assert_eq!(
+      run(source, vec![3, 8]),
+      vec![-5, -1, 1, 0, 1, 0, 0, 1, 1, 0]
+    );
+    assert_eq!(run(source, vec![4, 4]), vec![0, -8, 0, 0, 1, 1, 1, 0, 1, 0]);
+  }
+
+  #[test]
+  fn nested_calls_keep_temporaries() {
+    let source = "
+      fn add(a, b) { return a + b; }
+      fn twice(x) { return add(x, x); }
+      fn main() {
+        output(add(twice(2), add(1, twice(3))) * 2);
+      }
+    ";
+    assert_eq!(run(source, vec![]), vec![22]);
+  }
+
+  #[test]
+  fn variables_are_scoped_to_their_block() {
+    let source = "
+      fn main() {
+        let n = input();
+        if n > 0 {
+          let a = n * 2;
+          output(a);
+        } else {
+          let a = 7;
+          output(a);
+        }
+        while n > 0 {
+          let b = n;
+          n = n - 1;
+          output(b);
+        }
+        let c = 5;
+        output(c + n);
+      }
+    ";
+    assert_eq!(run(source, vec![2]), vec![4, 2, 1, 5]);
+    assert_eq!(run(source, vec![0]), vec![7, 5]);
+    assert_eq!(
+      error("fn main() {\n  if 1 { let a = 1; }\n  output(a);\n}"),
+      "3:10: Unknown variable a"
+    );
+    assert_eq!(
+      error("fn main() {\n  let a = 1;\n  while a { let a = 2; }\n}"),
+      "3:13: Variable a is already declared"
+    );
+  }
+
+  #[test]
+  fn deep_recursion_exhausts_the_stack() {
+    let source = "
+      fn depth(n) { return 1 + depth(n + 1); }
+      fn main() { output(depth(0)); }
+    ";
+    let error = compile_program(source).unwrap().run(None).unwrap_err();
+    assert!(
+      error.to_string().starts_with("Stack exhausted: "),
+      "{}",
+      error
+    );
+    assert!(
+      error.to_string().ends_with("is outside memory"),
+      "{}",
+      error
+    );
+
+    // Other faults are left as they are
+    let source = "fn main() { output(input()); }";
+    let error = compile_program(source).unwrap().run(None).unwrap_err();
+    assert!(error.to_string().starts_with("SaveInput at "), "{}", error);
+  }
+
+  #[test]
+  fn errors_report_positions() {
+    assert_eq!(
+      error("fn main() {\n  output(x);\n}"),
+      "2:10: Unknown variable x"
+    );
+    assert_eq!(
+      error("fn main() {\n  let a = 1;\n  let a = 2;\n}"),
+      "3:3: Variable a is already declared"
+    );
+    assert_eq!(
+      error("fn f(a) { return a; }\nfn main() { f(); }"),
+      "2:13: f takes 1 arguments but 0 were given"
+    );
+    assert_eq!(
+      error("fn main() { let = 1; }"),
+      "1:17: Expected a name, found Assign"
+    );
+    assert_eq!(error("fn other() {}"), "Missing main function");
+  }
+}
@@ -0,0 +1,311 @@
+use crate::Result;
+
+use super::{lexer::Token, Position};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnaryOperator {
+  Negate,
+  Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryOperator {
+  Add,
+  Subtract,
+  Multiply,
+  Less,
+  LessEqual,
+  Greater,
+  GreaterEqual,
+  Equal,
+  NotEqual,
+  And,
+  Or,
+}
+
+#[derive(Debug)]
+pub enum Expression {
+  Number(isize),
+  Variable(String, Position),
+  Input,
+  Unary(UnaryOperator, Box<Expression>),
+  Binary(BinaryOperator, Box<Expression>, Box<Expression>),
+  Call(String, Vec<Expression>, Position),
+}
+
+#[derive(Debug)]
+pub enum Statement {
+  Let(String, Expression, Position),
+  Assign(String, Expression, Position),
+  If(Expression, Vec<Statement>, Vec<Statement>),
+  While(Expression, Vec<Statement>),
+  Return(Option<Expression>),
+  Output(Expression),
+  Expression(Expression),
+}
+
+#[derive(Debug)]
+pub struct Function {
+  pub name: String,
+  pub parameters: Vec<String>,
+  pub body: Vec<Statement>,
+  pub position: Position,
+}
+
+/// Binary operators from loosest to tightest binding.
+const PRECEDENCE: [&[(Token, BinaryOperator)]; 5] = [
+  &[(Token::Or, BinaryOperator::Or)],
+  &[(Token::And, BinaryOperator::And)],
+  &[
+    (Token::Equal, BinaryOperator::Equal),
+    (Token::NotEqual, BinaryOperator::NotEqual),
+  ],
+  &[
+    (Token::Less, BinaryOperator::Less),
+    (Token::LessEqual, BinaryOperator::LessEqual),
+    (Token::Greater, BinaryOperator::Greater),
+    (Token::GreaterEqual, BinaryOperator::GreaterEqual),
+  ],
+  &[
+    (Token::Plus, BinaryOperator::Add),
+    (Token::Minus, BinaryOperator::Subtract),
+  ],
+];
+
+pub struct Parser {
+  tokens: Vec<(Token, Position)>,
+  index: usize,
+}
+
+impl Parser {
+  pub fn new(tokens: Vec<(Token, Position)>) -> Parser {
+    Parser { tokens, index: 0 }
+  }
+
+  fn peek(&self) -> &Token {
+    &self.tokens[self.index].0
+  }
+
+  fn position(&self) -> Position {
+    self.tokens[self.index].1
+  }
+
+  fn advance(&mut self) -> Token {
+    let token = self.tokens[self.index].0.clone();
+    if token != Token::End {
+      self.index += 1;
+    }
+    token
+  }
+
+  fn eat(&mut self, token: &Token) -> bool {
+    if self.peek() == token {
+      self.advance();
+      return true;
+    }
+    false
+  }
+
+  fn expect(&mut self, token: Token) -> Result<()> {
+    if self.eat(&token) {
+      return Ok(());
+    }
+    Err(
+      format!(
+        "{}: Expected {:?}, found {:?}",
+        self.position(),
+        token,
+        self.peek()
+      )
+      .into(),
+    )
+  }
+
+  fn identifier(&mut self) -> Result<String> {
+    let position = self.position();
+    match self.advance() {
+      Token::Identifier(name) => Ok(name),
+      token => Err(format!("{}: Expected a name, found {:?}", position, token).into()),
+    }
+  }
+
+  pub fn program(&mut self) -> Result<Vec<Function>> {
+    let mut functions = vec![];
+    while self.peek() != &Token::End {
+      functions.push(self.function()?);
+    }
+    Ok(functions)
+  }
+
+  fn function(&mut self) -> Result<Function> {
+    let position = self.position();
+    self.expect(Token::Fn)?;
+    let name = self.identifier()?;
+
+    self.expect(Token::LeftParen)?;
+    let mut parameters = vec![];
+    if !self.eat(&Token::RightParen) {
+      loop {
+        parameters.push(self.identifier()?);
+        if self.eat(&Token::RightParen) {
+          break;
+        }
+        self.expect(Token::Comma)?;
+      }
+    }
+
+    Ok(Function {
+      name,
+      parameters,
+      body: self.block()?,
+      position,
+    })
+  }
+
+  fn block(&mut self) -> Result<Vec<Statement>> {
+    self.expect(Token::LeftBrace)?;
+    let mut statements = vec![];
+    while !self.eat(&Token::RightBrace) {
+      statements.push(self.statement()?);
+    }
+    Ok(statements)
+  }
+
+  fn statement(&mut self) -> Result<Statement> {
+    let position = self.position();
+    match self.peek().clone() {
+      Token::Let => {
+        self.advance();
+        let name = self.identifier()?;
+        self.expect(Token::Assign)?;
+        let value = self.expression()?;
+        self.expect(Token::Semicolon)?;
+        Ok(Statement::Let(name, value, position))
+      }
+      Token::If => {
+        self.advance();
+        let condition = self.expression()?;
+        let then_branch = self.block()?;
+        let else_branch = match self.eat(&Token::Else) {
+          true if self.peek() == &Token::If => vec![self.statement()?],
+          true => self.block()?,
+          false => vec![],
+        };
+        Ok(Statement::If(condition, then_branch, else_branch))
+      }
+      Token::While => {
+        self.advance();
+        let condition = self.expression()?;
+        Ok(Statement::While(condition, self.block()?))
+      }
+      Token::Return => {
+        self.advance();
+        let value = match self.peek() {
+          Token::Semicolon => None,
+          _ => Some(self.expression()?),
+        };
+        self.expect(Token::Semicolon)?;
+        Ok(Statement::Return(value))
+      }
+      Token::Identifier(name) if self.tokens[self.index + 1].0 == Token::Assign => {
+        self.advance();
+        self.advance();
+        let value = self.expression()?;
+        self.expect(Token::Semicolon)?;
+        Ok(Statement::Assign(name, value, position))
+      }
+      Token::Identifier(name) if name == "output" => {
+        self.advance();
+        self.expect(Token::LeftParen)?;
+        let value = self.expression()?;
+        self.expect(Token::RightParen)?;
+        self.expect(Token::Semicolon)?;
+        Ok(Statement::Output(value))
+      }
+      _ => {
+        let expression = self.expression()?;
+        self.expect(Token::Semicolon)?;
+        Ok(Statement::Expression(expression))
+      }
+    }
+  }
+
+  pub fn expression(&mut self) -> Result<Expression> {
+    self.binary(0)
+  }
+
+  fn binary(&mut self, level: usize) -> Result<Expression> {
+    if level == PRECEDENCE.len() {
+      return self.product();
+    }
+
+    let mut left = self.binary(level + 1)?;
+    while let Some((_, operator)) = PRECEDENCE[level]
+      .iter()
+      .find(|(token, _)| token == self.peek())
+    {
+      self.advance();
+      let right = self.binary(level + 1)?;
+      left = Expression::Binary(*operator, Box::new(left), Box::new(right));
+    }
+    Ok(left)
+  }
+
+  fn product(&mut self) -> Result<Expression> {
+    let mut left = self.unary()?;
+    while self.eat(&Token::Star) {
+      let right = self.unary()?;
+      left = Expression::Binary(BinaryOperator::Multiply, Box::new(left), Box::new(right));
+    }
+    Ok(left)
+  }
+
+  fn unary(&mut self) -> Result<Expression> {
+    if self.eat(&Token::Minus) {
+      return Ok(Expression::Unary(
+        UnaryOperator::Negate,
+        Box::new(self.unary()?),
+      ));
+    }
+    if self.eat(&Token::Not) {
+      return Ok(Expression::Unary(
+        UnaryOperator::Not,
+        Box::new(self.unary()?),
+      ));
+    }
+    self.primary()
+  }
+
+  fn primary(&mut self) -> Result<Expression> {
+    let position = self.position();
+    match self.advance() {
+      Token::Number(number) => Ok(Expression::Number(number)),
+      Token::LeftParen => {
+        let expression = self.expression()?;
+        self.expect(Token::RightParen)?;
+        Ok(expression)
+      }
+      Token::Identifier(name) if name == "input" => {
+        self.expect(Token::LeftParen)?;
+        self.expect(Token::RightParen)?;
+        Ok(Expression::Input)
+      }
+      Token::Identifier(name) if self.peek() == &Token::LeftParen => {
+        self.advance();
+        let mut arguments = vec![];
+        if !self.eat(&Token::RightParen) {
+          loop {
+            arguments.push(self.expression()?);
+            if self.eat(&Token::RightParen) {
+              break;
+            }
+            self.expect(Token::Comma)?;
+          }
+        }
+        Ok(Expression::Call(name, arguments, position))
+      }
+      Token::Identifier(name) => Ok(Expression::Variable(name, position)),
+      token => Err(format!("{}: Expected an expression, found {:?}", position, token).into()),
+    }
+  }
+}
@@ -0,0 +1,217 @@
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap};
+
+use crate::intcode::{instruction::Instruction, opcode::Opcode, parameter::ParameterMode};
+
+pub struct Decoded {
+  pub address: usize,
+  pub instruction: Instruction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flow {
+  Next,
+  Halt,
+  Jump {
+    /// `None` when the target is read from memory, e.g. a return address.
+    target: Option<usize>,
+    conditional: bool,
+  },
+  /// An unconditional jump preceded by storing the address after it, i.e. a subroutine call.
+  Call {
+    target: usize,
+    return_to: usize,
+  },
+}
+
+impl Decoded {
+  pub fn opcode(&self) -> Opcode {
+    self.instruction.opcode
+  }
+
+  pub fn end(&self) -> usize {
+    self.address + self.instruction.parameters.len() + 1
+  }
+
+  fn immediate(&self, index: usize) -> Option<isize> {
+    let parameter = self.instruction.parameters.get(index)?;
+    (parameter.mode == ParameterMode::Immediate).then_some(parameter.address_or_value)
+  }
+
+  /// Jumps whose condition is an immediate that always holds.
+  pub fn is_unconditional_jump(&self) -> bool {
+    match (self.opcode(), self.immediate(0)) {
+      (Opcode::JumpIfTrue, Some(condition)) => condition != 0,
+      (Opcode::JumpIfFalse, Some(condition)) => condition == 0,
+      _ => false,
+    }
+  }
+
+  /// Value written by an add or multiply of two immediates, `None` if it overflows as running it
+  /// would fault instead.
+  pub fn constant_write(&self) -> Option<isize> {
+    let (a, b) = (self.immediate(0)?, self.immediate(1)?);
+    match self.opcode() {
+      Opcode::Add => a.checked_add(b),
+      Opcode::Multiply => a.checked_mul(b),
+      _ => None,
+    }
+  }
+
+  /// Relative base change of an immediate adjustment.
+  pub fn frame_adjustment(&self) -> Option<isize> {
+    match self.opcode() {
+      Opcode::AdjustRelativeBase => self.immediate(0),
+      _ => None,
+    }
+  }
+}
+
+/// Decodes the instruction at `address`, `None` if it isn't a valid built-in instruction.
+fn decode(memory: &[isize], address: usize) -> Option<Decoded> {
+  let opcode = Opcode::from_first_value(*memory.get(address)?).ok()?;
+  let ints = memory.get(address..=address + opcode.parameter_count())?;
+  Some(Decoded {
+    address,
+    instruction: Instruction::new(opcode, ints).ok()?,
+  })
+}
+
+/// Instructions reachable from address 0, with their control flow, subroutine entry points and the
+/// relative base offset from the start of their subroutine where it is statically known.
+pub struct Listing {
+  pub instructions: BTreeMap<usize, Decoded>,
+  pub flows: HashMap<usize, Flow>,
+  pub frame_offsets: HashMap<usize, Option<isize>>,
+  pub functions: BTreeSet<usize>,
+}
+
+impl Listing {
+  pub fn successors(&self, address: usize) -> Vec<usize> {
+    let end = self.instructions[&address].end();
+    match self.flows[&address] {
+      Flow::Next => vec![end],
+      Flow::Halt => vec![],
+      Flow::Jump {
+        target,
+        conditional,
+      } => target
+        .into_iter()
+        .chain(conditional.then_some(end))
+        .collect(),
+      Flow::Call { return_to, .. } => vec![return_to],
+    }
+  }
+
+  /// Addresses of the instructions belonging to the subroutine starting at `entry`, not
+  /// descending into calls.
+  pub fn function_body(&self, entry: usize) -> BTreeSet<usize> {
+    let mut body = BTreeSet::new();
+    let mut pending = vec![entry];
+    while let Some(address) = pending.pop() {
+      if self.instructions.contains_key(&address) && body.insert(address) {
+        pending.extend(self.successors(address));
+      }
+    }
+    body
+  }
+
+  /// The instruction ending right where `address` starts, skipping immediate frame adjustments.
+  fn setup_before(&self, address: usize) -> Option<&Decoded> {
+    let previous = self
+      .instructions
+      .range(..address)
+      .next_back()
+      .map(|(_, decoded)| decoded)
+      .filter(|decoded| decoded.end() == address)?;
+    match previous.frame_adjustment() {
+      Some(_) => self.setup_before(previous.address),
+      None => Some(previous),
+    }
+  }
+
+  fn flow(&self, decoded: &Decoded) -> Flow {
+    match decoded.opcode() {
+      Opcode::Halt => Flow::Halt,
+      Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+        let target = decoded
+          .immediate(1)
+          .and_then(|target| usize::try_from(target).ok());
+        let conditional = !decoded.is_unconditional_jump();
+
+        if let (Some(target), false) = (target, conditional) {
+          let return_to = decoded.end() as isize;
+          let stores_return = self
+            .setup_before(decoded.address)
+            .is_some_and(|setup| setup.constant_write() == Some(return_to));
+          if stores_return {
+            return Flow::Call {
+              target,
+              return_to: decoded.end(),
+            };
+          }
+        }
+        Flow::Jump {
+          target,
+          conditional,
+        }
+      }
+      _ => Flow::Next,
+    }
+  }
+}
+
+pub fn disassemble(memory: &[isize]) -> Listing {
+  let mut listing = Listing {
+    instructions: BTreeMap::new(),
+    flows: HashMap::new(),
+    frame_offsets: HashMap::new(),
+    functions: BTreeSet::from([0]),
+  };
+
+  // Fall-through successors are pushed last so straight-line code is decoded in order, which
+  // call detection relies on
+  let mut pending: Vec<(usize, Option<isize>)> = vec![(0, Some(0))];
+  while let Some((address, offset)) = pending.pop() {
+    let offset = match listing.frame_offsets.get(&address) {
+      None => offset,
+      Some(known) if *known == offset => continue,
+      // Reached with different frame offsets, so it is not statically known
+      Some(None) => continue,
+      Some(Some(_)) => None,
+    };
+    listing.frame_offsets.insert(address, offset);
+
+    if let Entry::Vacant(vacant) = listing.instructions.entry(address) {
+      match decode(memory, address) {
+        Some(decoded) => vacant.insert(decoded),
+        None => continue,
+      };
+    }
+
+    let decoded = &listing.instructions[&address];
+    let flow = listing.flow(decoded);
+    let next_offset = match (decoded.opcode(), decoded.frame_adjustment()) {
+      // An offset that overflows isn't statically known either
+      (Opcode::AdjustRelativeBase, Some(adjustment)) => {
+        offset.and_then(|o| o.checked_add(adjustment))
+      }
+      (Opcode::AdjustRelativeBase, None) => None,
+      _ => offset,
+    };
+    listing.flows.insert(address, flow);
+
+    match flow {
+      Flow::Call { target, return_to } => {
+        listing.functions.insert(target);
+        pending.push((target, Some(0)));
+        pending.push((return_to, next_offset));
+      }
+      _ => {
+        let successors = listing.successors(address);
+        pending.extend(successors.into_iter().rev().map(|s| (s, next_offset)));
+      }
+    }
+  }
+
+  listing
+}
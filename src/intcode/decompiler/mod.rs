@@ -0,0 +1,430 @@
+//! Decompiler lifting Intcode back to structured pseudocode.
+//!
+//! Code is found by following control flow from address 0, so data mixed in with it is never
+//! decoded. Unconditional jumps right after storing the address following them are subroutine
+//! calls, and immediate `AdjustRelativeBase` instructions move the subroutine's stack frame, which
+//! is how relative parameters are resolved to named frame slots:
+//!
+//! ```text
+//! fn sub_920(arg_1) {  // frame size 3
+//!   if arg_1 < 3 {
+//!     ...
+//!     return;
+//!   }
+//!   ...
+//! }
+//! ```
+//!
+//! Loops are recovered from jumps back to earlier code and if/else from forward jumps over a
+//! block. Jumps that fit neither are kept as `goto`. Comparisons whose result is only used by the
+//! jump after them are folded into its condition, and memory cells used more than once are named
+//! `var_<address>`.
+
+use std::{
+  collections::{BTreeSet, HashMap},
+  fmt::Write,
+};
+
+use super::opcode::Opcode;
+
+use self::{
+  disassembly::{disassemble, Listing},
+  structure::{accesses, resolve, Branch, Cell, Function, Node, Operand, Statement},
+};
+
+mod disassembly;
+mod structure;
+
+/// Renders operands and statements of one function.
+struct Renderer<'a> {
+  listing: &'a Listing,
+  function: &'a Function,
+  references: &'a HashMap<isize, usize>,
+  labels: BTreeSet<usize>,
+}
+
+impl Renderer<'_> {
+  fn cell(&self, cell: Cell) -> String {
+    match cell {
+      Cell::Absolute(address) if self.references.get(&address).copied().unwrap_or(0) > 1 => {
+        format!("var_{}", address)
+      }
+      Cell::Absolute(address) => format!("mem[{}]", address),
+      Cell::Slot(0) => "return_address".to_string(),
+      Cell::Slot(slot) if self.function.arguments.contains(&slot) => format!("arg_{}", slot),
+      Cell::Slot(slot) if slot > 0 => format!("local_{}", slot),
+      Cell::Slot(slot) => format!("frame[{}]", slot),
+      Cell::Relative(offset) => format!("rb[{}]", offset),
+    }
+  }
+
+  fn operand(&self, address: usize, index: usize) -> String {
+    let parameter = &self.listing.instructions[&address].instruction.parameters[index];
+    match resolve(self.listing, self.function.entry, address, parameter) {
+      Operand::Value(value) => value.to_string(),
+      Operand::Cell(cell) => self.cell(cell),
+    }
+  }
+
+  fn immediate(&self, address: usize, index: usize) -> Option<isize> {
+    let parameter = &self.listing.instructions[&address].instruction.parameters[index];
+    match resolve(self.listing, self.function.entry, address, parameter) {
+      Operand::Value(value) => Some(value),
+      Operand::Cell(_) => None,
+    }
+  }
+
+  /// The instruction at `address` as its opcode applied to its operands, for when it doesn't fit
+  /// the statement it's used in.
+  fn raw(&self, address: usize) -> String {
+    let decoded = &self.listing.instructions[&address];
+    let operands: Vec<String> = (0..decoded.instruction.parameters.len())
+      .map(|index| self.operand(address, index))
+      .collect();
+    format!("{:?}({})", decoded.opcode(), operands.join(", "))
+  }
+
+  /// The value computed by the instruction at `address`.
+  fn expression(&self, address: usize) -> String {
+    let (a, b) = (self.immediate(address, 0), self.immediate(address, 1));
+    match self.listing.instructions[&address].opcode() {
+      Opcode::SaveInput => "input()".to_string(),
+      Opcode::Add if a == Some(0) => self.operand(address, 1),
+      Opcode::Add if b == Some(0) => self.operand(address, 0),
+      Opcode::Add => match b {
+        Some(b) if b < 0 => format!("{} - {}", self.operand(address, 0), b.unsigned_abs()),
+        _ => format!(
+          "{} + {}",
+          self.operand(address, 0),
+          self.operand(address, 1)
+        ),
+      },
+      Opcode::Multiply if a == Some(1) => self.operand(address, 1),
+      Opcode::Multiply if b == Some(1) => self.operand(address, 0),
+      Opcode::Multiply if a == Some(-1) => format!("-{}", self.operand(address, 1)),
+      Opcode::Multiply if b == Some(-1) => format!("-{}", self.operand(address, 0)),
+      Opcode::Multiply => format!(
+        "{} * {}",
+        self.operand(address, 0),
+        self.operand(address, 1)
+      ),
+      Opcode::LessThan => format!(
+        "{} < {}",
+        self.operand(address, 0),
+        self.operand(address, 1)
+      ),
+      Opcode::Equals => format!(
+        "{} == {}",
+        self.operand(address, 0),
+        self.operand(address, 1)
+      ),
+      _ => self.raw(address),
+    }
+  }
+
+  fn instruction(&self, address: usize) -> String {
+    let opcode = self.listing.instructions[&address].opcode();
+    match opcode {
+      Opcode::SaveInput => format!("{} = input();", self.operand(address, 0)),
+      Opcode::Output => format!("output({});", self.operand(address, 0)),
+      Opcode::AdjustRelativeBase => format!("rb += {};", self.operand(address, 0)),
+      Opcode::JumpIfTrue | Opcode::JumpIfFalse => format!("goto *{};", self.operand(address, 1)),
+      Opcode::Halt => "halt;".to_string(),
+      _ => format!(
+        "{} = {};",
+        self.operand(address, 2),
+        self.expression(address)
+      ),
+    }
+  }
+
+  fn condition(&self, branch: Branch) -> String {
+    let jump_if_true = self.listing.instructions[&branch.jump].opcode() == Opcode::JumpIfTrue;
+    let holds = jump_if_true != branch.negated;
+    match branch.compare {
+      Some(compare) => {
+        let operator = match (self.listing.instructions[&compare].opcode(), holds) {
+          (Opcode::LessThan, true) => "<",
+          (Opcode::LessThan, false) => ">=",
+          (_, true) => "==",
+          (_, false) => "!=",
+        };
+        format!(
+          "{} {} {}",
+          self.operand(compare, 0),
+          operator,
+          self.operand(compare, 1)
+        )
+      }
+      None => format!(
+        "{} {} 0",
+        self.operand(branch.jump, 0),
+        if holds { "!=" } else { "==" }
+      ),
+    }
+  }
+
+  fn block(&mut self, nodes: &[Node], depth: usize, out: &mut String) {
+    for node in nodes {
+      self.node(node, depth, out);
+    }
+  }
+
+  fn line(&self, depth: usize, text: &str, out: &mut String) {
+    writeln!(out, "{}{}", "  ".repeat(depth), text).unwrap();
+  }
+
+  fn node(&mut self, node: &Node, depth: usize, out: &mut String) {
+    // Labels of goto targets go before the first statement at or after them
+    while let Some(&label) = self.labels.first().filter(|label| **label <= node.address) {
+      self.labels.remove(&label);
+      self.line(depth, &format!("L_{}:", label), out);
+    }
+
+    match &node.statement {
+      Statement::Instruction => self.line(depth, &self.instruction(node.address), out),
+      Statement::Call { target, arguments } => {
+        let arguments: Vec<String> = arguments.iter().map(|a| self.expression(*a)).collect();
+        let call = format!("{}({});", function_name(*target), arguments.join(", "));
+        self.line(depth, &call, out);
+      }
+      Statement::If(branch, then_branch, else_branch) => {
+        self.line(depth, &format!("if {} {{", self.condition(*branch)), out);
+        self.block(then_branch, depth + 1, out);
+        if !else_branch.is_empty() {
+          self.line(depth, "} else {", out);
+          self.block(else_branch, depth + 1, out);
+        }
+        self.line(depth, "}", out);
+      }
+      Statement::While(branch, body) => {
+        self.line(depth, &format!("while {} {{", self.condition(*branch)), out);
+        self.block(body, depth + 1, out);
+        self.line(depth, "}", out);
+      }
+      Statement::Loop(body) => {
+        self.line(depth, "loop {", out);
+        self.block(body, depth + 1, out);
+        self.line(depth, "}", out);
+      }
+      Statement::DoWhile(body, branch) => {
+        self.line(depth, "do {", out);
+        self.block(body, depth + 1, out);
+        self.line(
+          depth,
+          &format!("}} while {};", self.condition(*branch)),
+          out,
+        );
+      }
+      Statement::Break => self.line(depth, "break;", out),
+      Statement::Continue => self.line(depth, "continue;", out),
+      Statement::Goto(target) => self.line(depth, &format!("goto L_{};", target), out),
+      Statement::Return => self.line(depth, "return;", out),
+    }
+  }
+}
+
+fn function_name(entry: usize) -> String {
+  match entry {
+    0 => "main".to_string(),
+    _ => format!("sub_{}", entry),
+  }
+}
+
+fn goto_targets(nodes: &[Node], targets: &mut BTreeSet<usize>) {
+  for node in nodes {
+    match &node.statement {
+      Statement::Goto(target) => {
+        targets.insert(*target);
+      }
+      Statement::If(_, then_branch, else_branch) => {
+        goto_targets(then_branch, targets);
+        goto_targets(else_branch, targets);
+      }
+      Statement::While(_, body) | Statement::Loop(body) | Statement::DoWhile(body, _) => {
+        goto_targets(body, targets)
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Lifts the program in `memory` to pseudocode, one function per subroutine found.
+pub fn decompile(memory: &[isize]) -> String {
+  let listing = disassemble(memory);
+  let functions: Vec<Function> = listing
+    .functions
+    .iter()
+    .filter(|entry| listing.instructions.contains_key(entry))
+    .map(|entry| Function::new(&listing, *entry))
+    .collect();
+
+  // Cells referenced more than once are worth a name
+  let mut references: HashMap<isize, usize> = HashMap::new();
+  for function in &functions {
+    for address in &function.body {
+      for (_, cell, _) in accesses(&listing, function.entry, *address) {
+        if let Cell::Absolute(address) = cell {
+          *references.entry(address).or_insert(0) += 1;
+        }
+      }
+    }
+  }
+
+  let mut out = String::new();
+  for function in &functions {
+    let nodes = function.structure(&listing);
+    let mut labels = BTreeSet::new();
+    goto_targets(&nodes, &mut labels);
+
+    let arguments: Vec<String> = function
+      .arguments
+      .iter()
+      .map(|slot| format!("arg_{}", slot))
+      .collect();
+    let mut header = format!(
+      "fn {}({}) {{",
+      function_name(function.entry),
+      arguments.join(", ")
+    );
+    if !function.is_main() && function.frame_size != 0 {
+      write!(header, "  // frame size {}", function.frame_size).unwrap();
+    }
+    if !out.is_empty() {
+      out.push('\n');
+    }
+    out.push_str(&header);
+    out.push('\n');
+
+    let mut renderer = Renderer {
+      listing: &listing,
+      function,
+      references: &references,
+      labels,
+    };
+    renderer.block(&nodes, 1, &mut out);
+    out.push_str("}\n");
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{collections::HashSet, path::PathBuf};
+
+  use super::*;
+  use crate::intcode::{compiler::compile, program::Program};
+
+  #[test]
+  fn recovers_loops_and_branches() {
+    let memory = vec![
+      3, 30, // Input to [30]
+      1001, 30, -1, 30, // [30] -= 1
+      1008, 30, 5, 31, // [31] = [30] == 5
+      1006, 31, 18, // Skip the output unless [31]
+      4, 30, // Output [30]
+      1105, 1, 20, // Skip the else branch
+      104, 0, // Output 0
+      1005, 30, 2, // Loop while [30] != 0
+      99,
+    ];
+    let expected = "\
+fn main() {
+  var_30 = input();
+  do {
+    var_30 = var_30 - 1;
+    if var_30 == 5 {
+      output(var_30);
+    } else {
+      output(0);
+    }
+  } while var_30 != 0;
+  halt;
+}
+";
+    assert_eq!(decompile(&memory), expected);
+  }
+
+  #[test]
+  fn keeps_overflowing_writes_before_jumps() {
+    // Would store the address after the jump if the sum didn't overflow
+    let memory = vec![1101, isize::MAX, 1, 20, 1105, 1, 7, 99];
+    let pseudocode = decompile(&memory);
+    assert!(
+      pseudocode.starts_with("fn main() {\n  mem[20] = "),
+      "{}",
+      pseudocode
+    );
+    assert!(pseudocode.contains("halt;"), "{}", pseudocode);
+  }
+
+  #[test]
+  fn renders_extreme_operands() {
+    let subtracted = decompile(&[1101, 5, isize::MIN, 20, 99]);
+    assert!(
+      subtracted.contains(&format!("mem[20] = 5 - {};", isize::MIN.unsigned_abs())),
+      "{}",
+      subtracted
+    );
+
+    // Frame offsets and relative operands that overflow when combined, a jump into itself and one
+    // after an instruction with a single parameter
+    for memory in [
+      vec![109, isize::MAX, 109, 1, 99],
+      vec![109, 1, 201, isize::MAX, 0, 0, 99],
+      vec![1005, 4, 1, 99, 0],
+      vec![104, 5, 1105, 1, 5, 99],
+    ] {
+      let pseudocode = decompile(&memory);
+      assert!(pseudocode.contains("halt;"), "{}", pseudocode);
+    }
+  }
+
+  #[test]
+  fn recovers_compiled_functions() {
+    let source = "
+      fn factorial(n) {
+        if n < 2 { return 1; }
+        return n * factorial(n - 1);
+      }
+
+      fn main() {
+        let n = input();
+        while n > 0 {
+          output(factorial(n));
+          n = n - 1;
+        }
+      }
+    ";
+    let pseudocode = decompile(&compile(source).unwrap());
+    let headers: HashSet<&str> = pseudocode
+      .lines()
+      .filter(|line| line.starts_with("fn "))
+      .collect();
+
+    assert_eq!(headers.len(), 3, "{}", pseudocode);
+    assert!(
+      headers.iter().any(|h| h.contains("(arg_1)")),
+      "{}",
+      pseudocode
+    );
+    assert!(pseudocode.contains("while "), "{}", pseudocode);
+    assert!(pseudocode.contains("return;"), "{}", pseudocode);
+    assert!(!pseudocode.contains("goto"), "{}", pseudocode);
+  }
+
+  #[test]
+  fn recovers_day_9_subroutine() {
+    let program = Program::from_file(&PathBuf::from("src/input/2019/day_9.txt")).unwrap();
+    let pseudocode = decompile(&program.memory);
+
+    assert!(
+      pseudocode.contains("fn sub_920(arg_1) {  // frame size 3"),
+      "{}",
+      pseudocode
+    );
+    assert!(pseudocode.contains("if arg_1 >= 3 {"), "{}", pseudocode);
+    assert!(pseudocode.contains("sub_920(arg_1 - 1);"), "{}", pseudocode);
+    assert!(pseudocode.contains("return;"), "{}", pseudocode);
+  }
+}
@@ -0,0 +1,511 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::intcode::{
+  opcode::Opcode,
+  parameter::{Parameter, ParameterMode},
+};
+
+use super::disassembly::{Flow, Listing};
+
+/// A memory cell, with relative parameters resolved against the subroutine's frame where the
+/// relative base is statically known.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Cell {
+  Absolute(isize),
+  /// Offset from the relative base on entry to the subroutine.
+  Slot(isize),
+  /// Offset from a relative base that isn't statically known.
+  Relative(isize),
+}
+
+pub enum Operand {
+  Value(isize),
+  Cell(Cell),
+}
+
+/// A conditional jump, possibly with the comparison computing its condition folded into it.
+/// Stands for the jump being taken, or not taken if `negated`.
+#[derive(Clone, Copy)]
+pub struct Branch {
+  pub jump: usize,
+  pub compare: Option<usize>,
+  pub negated: bool,
+}
+
+impl Branch {
+  fn negate(self) -> Branch {
+    Branch {
+      negated: !self.negated,
+      ..self
+    }
+  }
+}
+
+pub enum Statement {
+  /// The instruction at the node's address on its own.
+  Instruction,
+  Call {
+    target: usize,
+    arguments: Vec<usize>,
+  },
+  If(Branch, Vec<Node>, Vec<Node>),
+  While(Branch, Vec<Node>),
+  Loop(Vec<Node>),
+  DoWhile(Vec<Node>, Branch),
+  Break,
+  Continue,
+  Goto(usize),
+  Return,
+}
+
+pub struct Node {
+  pub address: usize,
+  pub statement: Statement,
+}
+
+pub struct Function {
+  pub entry: usize,
+  pub body: BTreeSet<usize>,
+  /// How far the subroutine moves the relative base past its entry value.
+  pub frame_size: isize,
+  /// Frame slots read before they are written.
+  pub arguments: BTreeSet<isize>,
+  /// Instructions absorbed into other statements: frame adjustments, call setup and folded
+  /// comparisons.
+  suppressed: HashSet<usize>,
+  /// Comparison folded into the conditional jump right after it, by jump address.
+  folded: HashMap<usize, usize>,
+  /// Instructions storing a call's arguments, by call address and ordered by slot.
+  call_arguments: HashMap<usize, Vec<usize>>,
+}
+
+pub fn resolve(listing: &Listing, entry: usize, address: usize, parameter: &Parameter) -> Operand {
+  let value = parameter.address_or_value;
+  let cell = match parameter.mode {
+    ParameterMode::Immediate => return Operand::Value(value),
+    ParameterMode::Position => Cell::Absolute(value),
+    ParameterMode::Relative => {
+      let slot = listing.frame_offsets[&address].and_then(|offset| offset.checked_add(value));
+      match slot {
+        // The relative base starts at 0, so the main routine's slots are absolute addresses
+        Some(slot) if entry == 0 => Cell::Absolute(slot),
+        Some(slot) => Cell::Slot(slot),
+        None => Cell::Relative(value),
+      }
+    }
+  };
+  Operand::Cell(cell)
+}
+
+/// Cells accessed by the instruction at `address`, with their parameter index and whether they
+/// are written.
+pub fn accesses(listing: &Listing, entry: usize, address: usize) -> Vec<(usize, Cell, bool)> {
+  let instruction = &listing.instructions[&address].instruction;
+  let write_mask = instruction.opcode.write_mask();
+  instruction
+    .parameters
+    .iter()
+    .enumerate()
+    .filter_map(
+      |(i, parameter)| match resolve(listing, entry, address, parameter) {
+        Operand::Cell(cell) => Some((i, cell, write_mask & (1 << i) != 0)),
+        Operand::Value(_) => None,
+      },
+    )
+    .collect()
+}
+
+fn is_comparison(opcode: Opcode) -> bool {
+  matches!(opcode, Opcode::LessThan | Opcode::Equals)
+}
+
+/// The comparison ending right before the conditional jump at `address` whose result the jump
+/// tests.
+fn feeding_comparison(listing: &Listing, entry: usize, address: usize) -> Option<usize> {
+  let jump = &listing.instructions[&address];
+  if !matches!(
+    listing.flows[&address],
+    Flow::Jump {
+      conditional: true,
+      ..
+    }
+  ) {
+    return None;
+  }
+  let (&compare, decoded) = listing.instructions.range(..address).next_back()?;
+  if decoded.end() != address || !is_comparison(decoded.opcode()) {
+    return None;
+  }
+  let result = resolve(listing, entry, compare, &decoded.instruction.parameters[2]);
+  let tested = resolve(listing, entry, address, &jump.instruction.parameters[0]);
+  match (result, tested) {
+    (Operand::Cell(result), Operand::Cell(tested)) if result == tested => Some(compare),
+    _ => None,
+  }
+}
+
+/// Whether `cell` is overwritten before being read again on every path after the jump at
+/// `address`, so the comparison storing its condition there can be folded into it. Absolute cells
+/// are followed into called subroutines, frame slots aren't as the callee names them differently.
+fn is_dead_after(listing: &Listing, entry: usize, address: usize, cell: Cell) -> bool {
+  let mut visited = HashSet::new();
+  let mut pending: Vec<(usize, usize)> = listing
+    .successors(address)
+    .into_iter()
+    .map(|next| (next, entry))
+    .collect();
+
+  while let Some((address, entry)) = pending.pop() {
+    if !listing.instructions.contains_key(&address) || !visited.insert((address, entry)) {
+      continue;
+    }
+    // Parameters are read before the result is written
+    let accessed = accesses(listing, entry, address);
+    if accessed.iter().any(|(_, c, write)| *c == cell && !write) {
+      return false;
+    }
+    if accessed.iter().any(|(_, c, write)| *c == cell && *write) {
+      continue;
+    }
+
+    pending.extend(
+      listing
+        .successors(address)
+        .into_iter()
+        .map(|next| (next, entry)),
+    );
+    if let (Flow::Call { target, .. }, Cell::Absolute(_)) = (listing.flows[&address], cell) {
+      pending.push((target, target));
+    }
+  }
+  true
+}
+
+/// The instruction ending right where `address` starts, if it is part of `body`.
+fn previous(listing: &Listing, body: &BTreeSet<usize>, address: usize) -> Option<usize> {
+  let (&previous, decoded) = listing.instructions.range(..address).next_back()?;
+  (decoded.end() == address && body.contains(&previous)).then_some(previous)
+}
+
+/// The instruction storing the return address of the call at `address` and those storing its
+/// arguments into the callee's frame, ordered by slot.
+fn call_setup(
+  listing: &Listing,
+  body: &BTreeSet<usize>,
+  address: usize,
+) -> (Option<usize>, Vec<usize>) {
+  let mut cursor = previous(listing, body, address);
+  while let Some(adjustment) =
+    cursor.filter(|a| listing.instructions[a].frame_adjustment().is_some())
+  {
+    cursor = previous(listing, body, adjustment);
+  }
+  let store = cursor;
+
+  let mut arguments: Vec<(isize, usize)> = vec![];
+  let callee_frame = listing.frame_offsets[&address];
+  cursor = store.and_then(|store| previous(listing, body, store));
+  while let Some(setup) = cursor {
+    let decoded = &listing.instructions[&setup];
+    let destination = match decoded.opcode() {
+      Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+        &decoded.instruction.parameters[2]
+      }
+      _ => break,
+    };
+    let slot = match (
+      destination.mode == ParameterMode::Relative,
+      listing.frame_offsets[&setup],
+      callee_frame,
+    ) {
+      (true, Some(offset), Some(callee_frame)) => {
+        let slot = offset
+          .checked_add(destination.address_or_value)
+          .and_then(|address| address.checked_sub(callee_frame));
+        match slot {
+          Some(slot) => slot,
+          None => break,
+        }
+      }
+      _ => break,
+    };
+    if slot < 1 || arguments.iter().any(|(s, _)| *s == slot) {
+      break;
+    }
+    arguments.push((slot, setup));
+    cursor = previous(listing, body, setup);
+  }
+
+  arguments.sort();
+  (
+    store,
+    arguments.into_iter().map(|(_, setup)| setup).collect(),
+  )
+}
+
+impl Function {
+  pub fn new(listing: &Listing, entry: usize) -> Function {
+    let body = listing.function_body(entry);
+    let mut suppressed = HashSet::new();
+    let mut folded = HashMap::new();
+    let mut call_arguments = HashMap::new();
+
+    for &address in &body {
+      let decoded = &listing.instructions[&address];
+      if decoded.frame_adjustment().is_some() && listing.frame_offsets[&address].is_some() {
+        suppressed.insert(address);
+      }
+      if let Flow::Call { .. } = listing.flows[&address] {
+        let (store, arguments) = call_setup(listing, &body, address);
+        suppressed.extend(store);
+        suppressed.extend(arguments.iter().copied());
+        call_arguments.insert(address, arguments);
+      }
+      if let Some(compare) = feeding_comparison(listing, entry, address) {
+        let result = &listing.instructions[&compare].instruction.parameters[2];
+        if let Operand::Cell(cell) = resolve(listing, entry, compare, result) {
+          if is_dead_after(listing, entry, address, cell) {
+            suppressed.insert(compare);
+            folded.insert(address, compare);
+          }
+        }
+      }
+    }
+
+    let mut written = HashSet::new();
+    let mut arguments = BTreeSet::new();
+    for &address in &body {
+      for (_, cell, write) in accesses(listing, entry, address) {
+        match cell {
+          Cell::Slot(slot) if write => {
+            written.insert(slot);
+          }
+          Cell::Slot(slot) if slot > 0 && !written.contains(&slot) => {
+            arguments.insert(slot);
+          }
+          _ => {}
+        }
+      }
+    }
+    let frame_size = body
+      .iter()
+      .filter_map(|address| listing.frame_offsets[address])
+      .max()
+      .unwrap_or(0);
+
+    Function {
+      entry,
+      body,
+      frame_size,
+      arguments,
+      suppressed,
+      folded,
+      call_arguments,
+    }
+  }
+
+  pub fn is_main(&self) -> bool {
+    self.entry == 0
+  }
+
+  pub fn structure(&self, listing: &Listing) -> Vec<Node> {
+    let end = self
+      .body
+      .last()
+      .map_or(0, |last| listing.instructions[last].end());
+    Structurer {
+      listing,
+      function: self,
+      loops: vec![],
+    }
+    .block(self.body.first().copied().unwrap_or(self.entry), end)
+  }
+}
+
+/// Recovers structured control flow from the instructions of a function, in address order.
+struct Structurer<'a> {
+  listing: &'a Listing,
+  function: &'a Function,
+  /// Head and exit address of the enclosing loops, innermost last.
+  loops: Vec<(usize, usize)>,
+}
+
+impl Structurer<'_> {
+  fn end_of(&self, address: usize) -> usize {
+    self.listing.instructions[&address].end()
+  }
+
+  fn branch(&self, jump: usize) -> Branch {
+    Branch {
+      jump,
+      compare: self.function.folded.get(&jump).copied(),
+      negated: false,
+    }
+  }
+
+  /// `break` or `continue` for a jump to `target` out of the innermost loop.
+  fn escape(&self, target: usize) -> Option<Statement> {
+    match self.loops.last() {
+      Some(&(_, exit)) if exit == target => Some(Statement::Break),
+      Some(&(head, _)) if head == target => Some(Statement::Continue),
+      _ => None,
+    }
+  }
+
+  /// The last jump back to `head` before `end`, if `head` starts a loop.
+  fn back_edge(&self, head: usize, end: usize) -> Option<usize> {
+    if self.loops.iter().any(|(h, _)| *h == head) {
+      return None;
+    }
+    self
+      .function
+      .body
+      .range(head..end)
+      .rev()
+      .find(|a| matches!(self.listing.flows[a], Flow::Jump { target: Some(t), .. } if t == head))
+      .copied()
+  }
+
+  fn block(&mut self, start: usize, end: usize) -> Vec<Node> {
+    let mut nodes = vec![];
+    let mut cursor = start;
+
+    while cursor < end {
+      let Some(&address) = self.function.body.range(cursor..end).next() else {
+        break;
+      };
+      cursor = self.end_of(address);
+
+      if let Some(back) = self.back_edge(address, end) {
+        cursor = self.end_of(back);
+        let statement = self.looped(address, back);
+        nodes.push(Node { address, statement });
+        continue;
+      }
+      if self.function.suppressed.contains(&address) {
+        continue;
+      }
+
+      let statement = match self.listing.flows[&address] {
+        Flow::Call { target, .. } => Statement::Call {
+          target,
+          arguments: self.function.call_arguments[&address].clone(),
+        },
+        Flow::Jump {
+          target,
+          conditional: false,
+        } => self.jump(address, target),
+        Flow::Jump {
+          target: Some(target),
+          conditional: true,
+        } => {
+          let branch = self.branch(address);
+          let next = self.end_of(address);
+          if let Some(escape) = self.escape(target) {
+            Statement::If(branch, vec![self.node(address, escape)], vec![])
+          } else if target >= next && target <= end {
+            let last_jump = self
+              .function
+              .body
+              .range(next..target)
+              .next_back()
+              .and_then(|&last| match self.listing.flows[&last] {
+                Flow::Jump {
+                  target: Some(after),
+                  conditional: false,
+                } if after > target && after <= end && self.escape(after).is_none() => {
+                  Some((last, after))
+                }
+                _ => None,
+              });
+            match last_jump {
+              Some((last, after)) => {
+                cursor = after;
+                let then_branch = self.block(next, last);
+                let else_branch = self.block(target, after);
+                match then_branch.is_empty() {
+                  true => Statement::If(branch, else_branch, then_branch),
+                  false => Statement::If(branch.negate(), then_branch, else_branch),
+                }
+              }
+              None => {
+                cursor = target;
+                Statement::If(branch.negate(), self.block(next, target), vec![])
+              }
+            }
+          } else {
+            Statement::If(
+              branch,
+              vec![self.node(address, Statement::Goto(target))],
+              vec![],
+            )
+          }
+        }
+        Flow::Jump {
+          target: None,
+          conditional: true,
+        } => Statement::If(
+          self.branch(address),
+          vec![self.node(address, Statement::Instruction)],
+          vec![],
+        ),
+        _ => Statement::Instruction,
+      };
+      nodes.push(Node { address, statement });
+    }
+
+    nodes
+  }
+
+  fn node(&self, address: usize, statement: Statement) -> Node {
+    Node { address, statement }
+  }
+
+  fn jump(&self, address: usize, target: Option<usize>) -> Statement {
+    match target {
+      Some(target) => self.escape(target).unwrap_or(Statement::Goto(target)),
+      None => {
+        let parameter = &self.listing.instructions[&address].instruction.parameters[1];
+        let entry = self.function.entry;
+        match resolve(self.listing, entry, address, parameter) {
+          // The return address is stored at the start of the frame
+          Operand::Cell(Cell::Slot(0)) if !self.function.is_main() => Statement::Return,
+          _ => Statement::Instruction,
+        }
+      }
+    }
+  }
+
+  fn looped(&mut self, head: usize, back: usize) -> Statement {
+    let exit = self.end_of(back);
+    self.loops.push((head, exit));
+
+    let statement = match self.listing.flows[&back] {
+      Flow::Jump {
+        conditional: true, ..
+      } => Statement::DoWhile(self.block(head, back), self.branch(back)),
+      _ => {
+        // A `while` loop starts by jumping to its exit, possibly after a folded comparison
+        let test = self
+          .function
+          .folded
+          .iter()
+          .find(|(_, compare)| **compare == head)
+          .map_or(head, |(jump, _)| *jump);
+        match self.listing.flows[&test] {
+          Flow::Jump {
+            target: Some(target),
+            conditional: true,
+          } if target == exit => {
+            let body = self.block(self.end_of(test), back);
+            Statement::While(self.branch(test).negate(), body)
+          }
+          _ => Statement::Loop(self.block(head, back)),
+        }
+      }
+    };
+
+    self.loops.pop();
+    statement
+  }
+}
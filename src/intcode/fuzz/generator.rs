@@ -0,0 +1,124 @@
+//! Random generation of well-formed Intcode programs.
+
+/// Instructions are placed at the start of memory, followed by a small data region that position
+/// and relative parameters point into.
+const MAX_INSTRUCTIONS: usize = 24;
+pub const DATA_START: usize = 100;
+const DATA_LEN: usize = 64;
+
+/// Opcode numbers with their parameter counts and whether the last parameter is written to.
+const OPCODES: [(isize, usize, bool); 10] = [
+  (1, 3, true),
+  (2, 3, true),
+  (3, 1, true),
+  (4, 1, false),
+  (5, 2, false),
+  (6, 2, false),
+  (7, 3, true),
+  (8, 3, true),
+  (9, 1, false),
+  (99, 0, false),
+];
+
+/// xorshift64* generator, so runs are reproducible from a seed without extra dependencies.
+pub struct Rng(u64);
+
+impl Rng {
+  pub fn new(seed: u64) -> Rng {
+    Rng(seed.max(1))
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.0 ^= self.0 >> 12;
+    self.0 ^= self.0 << 25;
+    self.0 ^= self.0 >> 27;
+    self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+  }
+
+  pub fn below(&mut self, bound: usize) -> usize {
+    (self.next_u64() % bound as u64) as usize
+  }
+
+  /// Inclusive range.
+  pub fn range(&mut self, low: isize, high: isize) -> isize {
+    low + self.below((high - low + 1) as usize) as isize
+  }
+
+  /// True with a probability of one in `n`.
+  pub fn one_in(&mut self, n: usize) -> bool {
+    self.below(n) == 0
+  }
+}
+
+pub struct Case {
+  pub memory: Vec<isize>,
+  pub inputs: Vec<isize>,
+  /// First values of the generated instructions, for coverage tracking.
+  pub first_values: Vec<isize>,
+}
+
+fn parameter(rng: &mut Rng, mode: isize, is_jump_target: bool, starts: &[usize]) -> isize {
+  match mode {
+    // Mostly the data region, occasionally the code itself to exercise self-modification
+    0 if rng.one_in(16) => rng.below(DATA_START) as isize,
+    0 => (DATA_START + rng.below(DATA_LEN)) as isize,
+    1 if is_jump_target => starts[rng.below(starts.len())] as isize,
+    1 => rng.range(-20, 20),
+    _ => rng.range(-8, 8),
+  }
+}
+
+/// Generates a program of random instructions ending in `Halt`, plus enough inputs that no
+/// bounded run can exhaust them.
+pub fn generate(rng: &mut Rng, max_steps: usize) -> Case {
+  let count = 1 + rng.below(MAX_INSTRUCTIONS);
+  let chosen = (0..count)
+    .map(|_| OPCODES[rng.below(OPCODES.len())])
+    .collect::<Vec<_>>();
+
+  // Point the relative base into the data region, then lay out instruction start addresses so
+  // immediate jumps can target them
+  let mut memory: Vec<isize> = vec![109, (DATA_START + DATA_LEN / 2) as isize];
+  let mut starts = vec![];
+  let mut first_values = vec![];
+  let mut address = memory.len();
+  for (_, parameter_count, _) in &chosen {
+    starts.push(address);
+    address += parameter_count + 1;
+  }
+  starts.push(address);
+
+  for (opcode, parameter_count, writes) in chosen {
+    let modes = (0..parameter_count)
+      .map(|i| {
+        let is_write = writes && i == parameter_count - 1;
+        match rng.below(3) as isize {
+          1 if is_write => 2 * rng.below(2) as isize,
+          mode => mode,
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let first_value = modes.iter().enumerate().fold(opcode, |value, (i, mode)| {
+      value + mode * 10_isize.pow(i as u32 + 2)
+    });
+    memory.push(first_value);
+    first_values.push(first_value);
+
+    for (i, mode) in modes.iter().enumerate() {
+      let is_jump_target = (opcode == 5 || opcode == 6) && i == 1;
+      memory.push(parameter(rng, *mode, is_jump_target, &starts));
+    }
+  }
+  memory.push(99);
+
+  memory.resize(DATA_START, 0);
+  memory.extend((0..DATA_LEN).map(|_| rng.range(-20, 20)));
+
+  let inputs = (0..max_steps).map(|_| rng.range(-20, 20)).collect();
+  Case {
+    memory,
+    inputs,
+    first_values,
+  }
+}
@@ -0,0 +1,237 @@
+//! Differential fuzzing of `Program` against an independent reference interpreter.
+//!
+//! Seeds can be overridden with `INTCODE_FUZZ_SEED` and the number of cases with
+//! `INTCODE_FUZZ_CASES`, e.g. `INTCODE_FUZZ_CASES=100000 cargo test fuzz`.
+
+use std::env;
+
+use super::{
+  limits::{InterruptReason, RunLimits, RunResult},
+  opcode::Opcode,
+  parameter::ParameterMode,
+  program::Program,
+};
+
+use self::generator::{generate, Case, Rng};
+
+mod generator;
+mod reference;
+
+const MAX_STEPS: usize = 200;
+
+#[derive(Debug, PartialEq)]
+pub struct State {
+  pub memory: Vec<isize>,
+  pub pointer: usize,
+  pub relative_base: isize,
+  pub outputs: Vec<isize>,
+}
+
+/// How a bounded run ended. Faults (errors from `Program`) compare equal regardless of their cause,
+/// as the two interpreters report them differently.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+  Halted(State),
+  StepLimit(State),
+  Fault,
+}
+
+fn state(program: Program, outputs: Vec<isize>) -> State {
+  State {
+    memory: program.memory,
+    pointer: program.pointer,
+    relative_base: program.relative_base,
+    outputs,
+  }
+}
+
+fn run_program(memory: &[isize], inputs: &[isize], max_steps: usize) -> Outcome {
+  // Bad programs have to end in errors, so a panic fails the test
+  let mut program = Program::from(memory.to_vec());
+  let limits = RunLimits::default().with_max_steps(max_steps);
+  match program.run_with_limits(Some(inputs.to_vec()), &limits) {
+    Ok(RunResult::Halted(outputs)) => Outcome::Halted(state(program, outputs)),
+    // The reference faults when it runs out of input
+    Ok(RunResult::Interrupted(interrupted))
+      if interrupted.reason == InterruptReason::AwaitingInput =>
+    {
+      Outcome::Fault
+    }
+    Ok(RunResult::Interrupted(interrupted)) => {
+      Outcome::StepLimit(state(interrupted.state, interrupted.outputs))
+    }
+    Err(_) => Outcome::Fault,
+  }
+}
+
+fn diverges(memory: &[isize], inputs: &[isize]) -> bool {
+  let memory = Program::from(memory.to_vec()).memory;
+  run_program(&memory, inputs, MAX_STEPS) != reference::run(&memory, inputs, MAX_STEPS)
+}
+
+/// Ordering used by the shrinker: zero, then halt, then by magnitude.
+fn complexity(value: isize) -> (bool, bool, usize) {
+  (value != 0, value != 99, value.unsigned_abs())
+}
+
+/// Greedily simplifies a diverging program one cell at a time until no single change keeps the
+/// divergence, so the reported case is small enough to read.
+fn shrink(case: Case) -> Case {
+  let Case {
+    mut memory,
+    mut inputs,
+    first_values,
+  } = case;
+
+  let mut progress = true;
+  while progress {
+    progress = false;
+
+    while memory.len() > 1 && diverges(&memory[..memory.len() - 1], &inputs) {
+      memory.pop();
+      progress = true;
+    }
+
+    for i in 0..memory.len() {
+      for candidate in [0, 99, memory[i] / 2] {
+        if complexity(candidate) >= complexity(memory[i]) {
+          continue;
+        }
+        let original = memory[i];
+        memory[i] = candidate;
+        if diverges(&memory, &inputs) {
+          progress = true;
+          break;
+        }
+        memory[i] = original;
+      }
+    }
+
+    for i in 0..inputs.len() {
+      if inputs[i] != 0 {
+        let original = inputs[i];
+        inputs[i] = 0;
+        if diverges(&memory, &inputs) {
+          progress = true;
+        } else {
+          inputs[i] = original;
+        }
+      }
+    }
+  }
+
+  Case {
+    memory,
+    inputs,
+    first_values,
+  }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+  env::var(name)
+    .ok()
+    .and_then(|value| value.parse().ok())
+    .unwrap_or(default)
+}
+
+fn opcode_from_number(number: isize) -> Option<Opcode> {
+  match number {
+    1 => Some(Opcode::Add),
+    2 => Some(Opcode::Multiply),
+    3 => Some(Opcode::SaveInput),
+    4 => Some(Opcode::Output),
+    5 => Some(Opcode::JumpIfTrue),
+    6 => Some(Opcode::JumpIfFalse),
+    7 => Some(Opcode::LessThan),
+    8 => Some(Opcode::Equals),
+    9 => Some(Opcode::AdjustRelativeBase),
+    99 => Some(Opcode::Halt),
+    _ => None,
+  }
+}
+
+#[test]
+fn program_matches_reference_interpreter() {
+  let seed = env_or("INTCODE_FUZZ_SEED", 0x1c0de);
+  let cases = env_or("INTCODE_FUZZ_CASES", 3000);
+  let mut rng = Rng::new(seed);
+  let mut opcodes_seen = [false; 100];
+  let mut modes_seen = [false; 3];
+
+  for case_number in 0..cases {
+    let case = generate(&mut rng, MAX_STEPS);
+    for value in &case.first_values {
+      let (opcode, modes) = reference::decode(*value).expect("Generated an invalid instruction");
+      opcodes_seen[opcode as usize] = true;
+      for mode in modes {
+        modes_seen[mode as usize] = true;
+      }
+    }
+
+    if diverges(&case.memory, &case.inputs) {
+      let minimal = shrink(case);
+      let memory = Program::from(minimal.memory.clone()).memory;
+      let used_inputs = minimal
+        .inputs
+        .iter()
+        .rposition(|input| *input != 0)
+        .map_or(0, |i| i + 1);
+      panic!(
+        "Divergence in case {} (seed {:#x})\nprogram: {:?}\ninputs (then zeroes): {:?}\nProgram: {:?}\nreference: {:?}",
+        case_number,
+        seed,
+        minimal.memory,
+        &minimal.inputs[..used_inputs],
+        run_program(&memory, &minimal.inputs, MAX_STEPS),
+        reference::run(&memory, &minimal.inputs, MAX_STEPS),
+      );
+    }
+  }
+
+  for opcode in [1, 2, 3, 4, 5, 6, 7, 8, 9, 99] {
+    assert!(
+      opcodes_seen[opcode],
+      "Opcode {} was never generated",
+      opcode
+    );
+  }
+  assert!(
+    modes_seen.iter().all(|seen| *seen),
+    "Not every parameter mode was generated"
+  );
+}
+
+#[test]
+fn opcode_decoding_matches_reference() {
+  let mut rng = Rng::new(env_or("INTCODE_FUZZ_SEED", 0x1c0de));
+  // Every mode digit combination for every valid opcode, plus arbitrary values for the rest
+  let values = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99]
+    .iter()
+    .flat_map(|opcode| (0..1000).map(move |modes| opcode + 100 * modes))
+    .chain((0..2000).map(|_| rng.range(-1000, 1_000_000)))
+    .collect::<Vec<_>>();
+
+  for value in values {
+    let opcode = Opcode::from_first_value(value).map_err(|_| ());
+    // Mode digits are not validated when decoding the opcode alone
+    let expected = match value {
+      0.. => opcode_from_number(value % 100).ok_or(()),
+      _ => Err(()),
+    };
+    assert_eq!(opcode, expected, "Opcode of {}", value);
+
+    if value < 0 {
+      continue;
+    }
+    for index in 0..3 {
+      let mode = ParameterMode::from_first_value(value, index).map_err(|_| ());
+      let expected = match reference::mode(value, index) {
+        Some(0) => Ok(ParameterMode::Position),
+        Some(1) => Ok(ParameterMode::Immediate),
+        Some(2) => Ok(ParameterMode::Relative),
+        _ => Err(()),
+      };
+      assert_eq!(mode, expected, "Mode of parameter {} of {}", index, value);
+    }
+  }
+}
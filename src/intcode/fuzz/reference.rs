@@ -0,0 +1,171 @@
+//! Minimal Intcode interpreter written straight from the puzzle descriptions, used as the oracle
+//! for differential testing. It deliberately shares no code with `Program` or `Instruction`.
+
+use super::{Outcome, State};
+
+/// Splits an instruction's first value into its opcode number and parameter modes.
+/// Returns `None` for negative values, unknown opcodes and unknown modes of used parameters.
+pub fn decode(value: isize) -> Option<(isize, Vec<isize>)> {
+  if value < 0 {
+    return None;
+  }
+
+  let opcode = value % 100;
+  let modes = (0..arity(opcode)?)
+    .map(|index| mode(value, index))
+    .collect::<Option<Vec<_>>>()?;
+  Some((opcode, modes))
+}
+
+/// Mode digit of parameter `index`, without validating the opcode.
+pub fn mode(value: isize, index: usize) -> Option<isize> {
+  let mode = (value / 10_isize.pow(index as u32 + 2)) % 10;
+  (mode <= 2).then_some(mode)
+}
+
+pub fn arity(opcode: isize) -> Option<usize> {
+  match opcode {
+    1 | 2 | 7 | 8 => Some(3),
+    5 | 6 => Some(2),
+    3 | 4 | 9 => Some(1),
+    99 => Some(0),
+    _ => None,
+  }
+}
+
+// Overflow is a fault
+fn add(a: isize, b: isize) -> Option<isize> {
+  a.checked_add(b)
+}
+
+fn multiply(a: isize, b: isize) -> Option<isize> {
+  a.checked_mul(b)
+}
+
+struct Machine {
+  memory: Vec<isize>,
+  ip: isize,
+  rb: isize,
+  outputs: Vec<isize>,
+}
+
+impl Machine {
+  fn read(&self, address: isize) -> Option<isize> {
+    usize::try_from(address)
+      .ok()
+      .and_then(|address| self.memory.get(address).copied())
+  }
+
+  fn write(&mut self, address: isize, value: isize) -> Option<()> {
+    let cell = usize::try_from(address)
+      .ok()
+      .and_then(|address| self.memory.get_mut(address))?;
+    *cell = value;
+    Some(())
+  }
+
+  fn address(&self, mode: isize, raw: isize) -> Option<isize> {
+    match mode {
+      0 => Some(raw),
+      2 => add(self.rb, raw),
+      _ => None,
+    }
+  }
+
+  fn value(&self, mode: isize, raw: isize) -> Option<isize> {
+    match mode {
+      1 => Some(raw),
+      _ => self.read(self.address(mode, raw)?),
+    }
+  }
+
+  /// Executes one instruction, returning `None` on any fault.
+  fn step(&mut self, opcode: isize, modes: &[isize], input: Option<isize>) -> Option<()> {
+    let raw = (1..=modes.len() as isize)
+      .map(|offset| self.read(self.ip + offset))
+      .collect::<Option<Vec<_>>>()?;
+    let mut next = self.ip + modes.len() as isize + 1;
+
+    match opcode {
+      1 | 2 | 7 | 8 => {
+        // All three parameters are read as values, including the one written to
+        let a = self.value(modes[0], raw[0])?;
+        let b = self.value(modes[1], raw[1])?;
+        self.value(modes[2], raw[2])?;
+        let result = match opcode {
+          1 => add(a, b)?,
+          2 => multiply(a, b)?,
+          7 => (a < b) as isize,
+          _ => (a == b) as isize,
+        };
+        let target = self.address(modes[2], raw[2])?;
+        self.write(target, result)?;
+      }
+      3 => {
+        let target = self.address(modes[0], raw[0])?;
+        self.write(target, input?)?;
+      }
+      4 => {
+        let value = self.value(modes[0], raw[0])?;
+        self.outputs.push(value);
+      }
+      5 | 6 => {
+        // The target is read even when the jump is not taken
+        let condition = self.value(modes[0], raw[0])?;
+        let target = self.value(modes[1], raw[1])?;
+        if (condition != 0) == (opcode == 5) {
+          if target < 0 {
+            return None;
+          }
+          next = target;
+        }
+      }
+      9 => {
+        let value = self.value(modes[0], raw[0])?;
+        self.rb = add(self.rb, value)?;
+      }
+      _ => return None,
+    }
+
+    self.ip = next;
+    Some(())
+  }
+
+  fn state(self) -> State {
+    State {
+      memory: self.memory,
+      pointer: self.ip as usize,
+      relative_base: self.rb,
+      outputs: self.outputs,
+    }
+  }
+}
+
+pub fn run(memory: &[isize], inputs: &[isize], max_steps: usize) -> Outcome {
+  let mut machine = Machine {
+    memory: memory.to_vec(),
+    ip: 0,
+    rb: 0,
+    outputs: vec![],
+  };
+  let mut inputs = inputs.iter().copied();
+  let mut steps = 0;
+
+  loop {
+    let Some((opcode, modes)) = machine.read(machine.ip).and_then(decode) else {
+      return Outcome::Fault;
+    };
+    if opcode == 99 {
+      return Outcome::Halted(machine.state());
+    }
+    if steps == max_steps {
+      return Outcome::StepLimit(machine.state());
+    }
+
+    let input = if opcode == 3 { inputs.next() } else { None };
+    if machine.step(opcode, &modes, input).is_none() {
+      return Outcome::Fault;
+    }
+    steps += 1;
+  }
+}
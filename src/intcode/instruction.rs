@@ -1,4 +1,6 @@
-use std::io::{self, Write};
+use std::sync::Arc;
+
+use crate::{Context, Error, Result};
 
 use super::{
   opcode::Opcode,
@@ -17,21 +19,21 @@ pub struct InstructionResult {
 }
 
 impl InstructionResult {
-  fn empty() -> InstructionResult {
+  pub fn empty() -> InstructionResult {
     InstructionResult {
       output: None,
       pointer: None,
     }
   }
 
-  fn from_output(output: isize) -> InstructionResult {
+  pub fn from_output(output: isize) -> InstructionResult {
     InstructionResult {
       output: Some(output),
       pointer: None,
     }
   }
 
-  fn from_jump(pointer: usize) -> InstructionResult {
+  pub fn from_jump(pointer: usize) -> InstructionResult {
     InstructionResult {
       output: None,
       pointer: Some(pointer),
@@ -39,107 +41,161 @@ impl InstructionResult {
   }
 }
 
-impl From<&[isize]> for Instruction {
-  fn from(ints: &[isize]) -> Instruction {
-    // First value must be positive
-    let opcode = Opcode::from_first_value(ints[0] as usize);
+/// The run's I/O as an instruction sees it: the inputs not read yet, which `SaveInput` and custom
+/// opcodes take from in order, and the outputs so far.
+pub struct InstructionIo<'a> {
+  inputs: &'a [isize],
+  read: usize,
+  pub outputs: &'a [isize],
+}
 
-    let parameters: Vec<Parameter> = (0..=opcode.parameter_count() - 1)
-      .map(|i| Parameter {
-        mode: ParameterMode::from_first_value(ints[0] as usize, i),
-        // Skip first value/opcode
-        address_or_value: ints[i + 1],
-      })
-      .collect::<Vec<_>>();
+impl<'a> InstructionIo<'a> {
+  pub fn new(inputs: &'a [isize], outputs: &'a [isize]) -> InstructionIo<'a> {
+    InstructionIo {
+      inputs,
+      read: 0,
+      outputs,
+    }
+  }
+
+  /// Takes the next input, `None` once they have all been read.
+  pub fn input(&mut self) -> Option<isize> {
+    let input = self.inputs.get(self.read).copied()?;
+    self.read += 1;
+    Some(input)
+  }
+
+  /// The inputs not read yet.
+  pub fn remaining(&self) -> &[isize] {
+    &self.inputs[self.read..]
+  }
+
+  /// How many inputs have been read.
+  pub fn read(&self) -> usize {
+    self.read
+  }
+}
+
+impl TryFrom<&[isize]> for Instruction {
+  type Error = Error;
 
-    Instruction { opcode, parameters }
+  fn try_from(ints: &[isize]) -> Result<Instruction> {
+    let first_value = *ints.first().context("Instruction is empty")?;
+    Instruction::new(Opcode::from_first_value(first_value)?, ints)
   }
 }
 
 impl Instruction {
-  fn map_parameter_values(&self, program: &Program) -> Vec<isize> {
+  /// `ints` starts at the instruction's first value, with the opcode already decoded from it.
+  pub fn new(opcode: Opcode, ints: &[isize]) -> Result<Instruction> {
+    let count = opcode.parameter_count();
+    if ints.len() <= count {
+      return Err(format!("{} needs {} parameters", opcode, count).into());
+    }
+
+    let parameters = (0..count)
+      .map(|i| {
+        Ok(Parameter {
+          mode: ParameterMode::from_first_value(ints[0], i)?,
+          // Skip first value/opcode
+          address_or_value: ints[i + 1],
+        })
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    Ok(Instruction { opcode, parameters })
+  }
+
+  fn map_parameter_values(&self, program: &Program) -> Result<Vec<isize>> {
     self
       .parameters
       .iter()
-      .map(|p| p.get_value(&program))
-      .collect::<Vec<_>>()
+      .map(|p| p.get_value(program))
+      .collect()
   }
 
-  fn set_result(&self, program: &mut Program, result: isize) {
-    let result_param = self.parameters.last().unwrap();
-    program.set(result_param.address(program), result);
+  fn set_result(&self, program: &mut Program, result: isize) -> Result<()> {
+    let result_param = self.parameters.last().context("No parameter to write to")?;
+    program.set(result_param.address(program)?, result)
   }
 
-  pub fn run(&self, program: &mut Program, input: Option<isize>) -> InstructionResult {
+  fn jump(target: isize) -> Result<InstructionResult> {
+    let pointer =
+      usize::try_from(target).map_err(|_| format!("Invalid jump address: {}", target))?;
+    Ok(InstructionResult::from_jump(pointer))
+  }
+
+  pub fn run(&self, program: &mut Program, io: &mut InstructionIo) -> Result<InstructionResult> {
     match self.opcode {
       Opcode::Add => {
-        let operands: Vec<isize> = self.map_parameter_values(program);
-        self.set_result(program, operands[0] + operands[1]);
-        InstructionResult::empty()
+        let operands: Vec<isize> = self.map_parameter_values(program)?;
+        let sum = operands[0]
+          .checked_add(operands[1])
+          .with_context(|| format!("{} + {} overflows", operands[0], operands[1]))?;
+        self.set_result(program, sum)?;
       }
       Opcode::Multiply => {
-        let operands: Vec<isize> = self.map_parameter_values(program);
-        self.set_result(program, operands[0] * operands[1]);
-        InstructionResult::empty()
+        let operands: Vec<isize> = self.map_parameter_values(program)?;
+        let product = operands[0]
+          .checked_mul(operands[1])
+          .with_context(|| format!("{} * {} overflows", operands[0], operands[1]))?;
+        self.set_result(program, product)?;
       }
       Opcode::SaveInput => {
-        if let Some(input) = input {
-          self.set_result(program, input);
-          return InstructionResult::empty();
-        }
-
-        let mut input_buffer = String::new();
-        print!("Enter input: ");
-        io::stdout().flush().unwrap();
-        io::stdin().read_line(&mut input_buffer).unwrap();
-        let input = input_buffer
-          .trim()
-          .parse::<isize>()
-          .expect("Invalid input, must be a number");
-
-        self.set_result(program, input);
-        InstructionResult::empty()
+        let input = io.input().context("Out of input")?;
+        self.set_result(program, input)?;
       }
       Opcode::Output => {
         let parameter = &self.parameters[0];
-        InstructionResult::from_output(parameter.get_value(program))
+        return Ok(InstructionResult::from_output(
+          parameter.get_value(program)?,
+        ));
       }
       Opcode::JumpIfTrue => {
-        let params: Vec<isize> = self.map_parameter_values(program);
+        let params: Vec<isize> = self.map_parameter_values(program)?;
         if params[0] != 0 {
-          let pointer: usize = params[1]
-            .try_into()
-            .expect("Invalid jump address (must be positive)");
-          return InstructionResult::from_jump(pointer);
+          return Instruction::jump(params[1]);
         }
-        InstructionResult::empty()
       }
       Opcode::JumpIfFalse => {
-        let params: Vec<isize> = self.map_parameter_values(program);
+        let params: Vec<isize> = self.map_parameter_values(program)?;
         if params[0] == 0 {
-          let pointer: usize = params[1]
-            .try_into()
-            .expect("Invalid jump address (must be positive)");
-          return InstructionResult::from_jump(pointer);
+          return Instruction::jump(params[1]);
         }
-        InstructionResult::empty()
       }
       Opcode::LessThan => {
-        let params: Vec<isize> = self.map_parameter_values(program);
-        self.set_result(program, (params[0] < params[1]) as isize);
-        InstructionResult::empty()
+        let params: Vec<isize> = self.map_parameter_values(program)?;
+        self.set_result(program, (params[0] < params[1]) as isize)?;
       }
       Opcode::Equals => {
-        let params: Vec<isize> = self.map_parameter_values(program);
-        self.set_result(program, (params[0] == params[1]) as isize);
-        InstructionResult::empty()
+        let params: Vec<isize> = self.map_parameter_values(program)?;
+        self.set_result(program, (params[0] == params[1]) as isize)?;
       }
       Opcode::AdjustRelativeBase => {
-        let params: Vec<isize> = self.map_parameter_values(program);
-        program.adjust_relative_base(params[0]);
-        InstructionResult::empty()
+        let params: Vec<isize> = self.map_parameter_values(program)?;
+        program.adjust_relative_base(params[0])?;
+      }
+      Opcode::Halt => {}
+      Opcode::Custom { code, .. } => {
+        // Written parameters are passed as addresses, the rest as values
+        let write_mask = self.opcode.write_mask();
+        let arguments = self
+          .parameters
+          .iter()
+          .enumerate()
+          .map(|(i, p)| match write_mask & (1 << i) {
+            0 => p.get_value(program),
+            _ => Ok(p.address(program)? as isize),
+          })
+          .collect::<Result<Vec<isize>>>()?;
+
+        let registry = Arc::clone(&program.opcodes);
+        let custom = registry
+          .get(code)
+          .with_context(|| format!("Custom opcode {} is not registered", code))?;
+        return (custom.handler)(program, &arguments, io);
       }
-      Opcode::Halt => InstructionResult::empty(),
     }
+    Ok(InstructionResult::empty())
   }
 }
@@ -0,0 +1,171 @@
+use std::{
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  time::{Duration, Instant},
+};
+
+use super::program::Program;
+
+/// Shared flag that stops a run from another thread. Clones refer to the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+  pub fn new() -> CancellationToken {
+    CancellationToken::default()
+  }
+
+  pub fn cancel(&self) {
+    self.0.store(true, Ordering::Relaxed);
+  }
+
+  pub fn is_cancelled(&self) -> bool {
+    self.0.load(Ordering::Relaxed)
+  }
+}
+
+/// Conditions that end a run before the program halts. No limits are set by default.
+#[derive(Clone, Debug, Default)]
+pub struct RunLimits {
+  pub max_steps: Option<usize>,
+  pub timeout: Option<Duration>,
+  pub cancellation: Option<CancellationToken>,
+}
+
+impl RunLimits {
+  pub fn with_max_steps(mut self, max_steps: usize) -> RunLimits {
+    self.max_steps = Some(max_steps);
+    self
+  }
+
+  pub fn with_timeout(mut self, timeout: Duration) -> RunLimits {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  pub fn with_cancellation(mut self, token: CancellationToken) -> RunLimits {
+    self.cancellation = Some(token);
+    self
+  }
+
+  /// Checked before every instruction, `steps` being the number of instructions run so far.
+  pub fn exceeded(&self, steps: usize, started: Instant) -> Option<InterruptReason> {
+    if self.cancellation.as_ref().is_some_and(|t| t.is_cancelled()) {
+      return Some(InterruptReason::Cancelled);
+    }
+    if self.max_steps.is_some_and(|max_steps| steps >= max_steps) {
+      return Some(InterruptReason::StepLimit);
+    }
+    if self
+      .timeout
+      .is_some_and(|timeout| started.elapsed() >= timeout)
+    {
+      return Some(InterruptReason::Timeout);
+    }
+    None
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterruptReason {
+  StepLimit,
+  Timeout,
+  Cancelled,
+  /// The next instruction reads input and none is left. Resuming the saved state with more input
+  /// continues the run, which is up to the caller, e.g. to prompt for it.
+  AwaitingInput,
+}
+
+/// A run that was stopped before halting, with the machine as it was before the next instruction.
+#[derive(Debug)]
+pub struct Interrupted {
+  pub reason: InterruptReason,
+  pub state: Program,
+  pub outputs: Vec<isize>,
+  pub steps: usize,
+}
+
+#[derive(Debug)]
+pub enum RunResult {
+  Halted(Vec<isize>),
+  Interrupted(Interrupted),
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{thread, time::Duration};
+
+  use super::*;
+
+  // Adds 1 to address 9 and jumps back to the start, forever
+  fn infinite_loop() -> Program {
+    Program::from(vec![1001, 9, 1, 9, 1105, 1, 0, 99, 0, 0])
+  }
+
+  fn interrupted(result: RunResult) -> Interrupted {
+    match result {
+      RunResult::Interrupted(interrupted) => interrupted,
+      RunResult::Halted(_) => panic!("Program halted"),
+    }
+  }
+
+  #[test]
+  fn step_limit_keeps_machine_state() {
+    let limits = RunLimits::default().with_max_steps(5);
+    let result = infinite_loop().run_with_limits(None, &limits).unwrap();
+    let interrupted = interrupted(result);
+
+    assert_eq!(interrupted.reason, InterruptReason::StepLimit);
+    assert_eq!(interrupted.steps, 5);
+    assert_eq!(interrupted.state.get(9).unwrap(), 3);
+    assert_eq!(interrupted.state.pointer, 4);
+  }
+
+  #[test]
+  fn timeout_interrupts_run() {
+    let limits = RunLimits::default().with_timeout(Duration::from_millis(10));
+    let result = infinite_loop().run_with_limits(None, &limits).unwrap();
+    assert_eq!(interrupted(result).reason, InterruptReason::Timeout);
+  }
+
+  #[test]
+  fn cancellation_from_another_thread() {
+    let token = CancellationToken::new();
+    let limits = RunLimits::default().with_cancellation(token.clone());
+    let handle = thread::spawn(move || infinite_loop().run_with_limits(None, &limits).unwrap());
+
+    thread::sleep(Duration::from_millis(10));
+    token.cancel();
+    assert_eq!(
+      interrupted(handle.join().unwrap()).reason,
+      InterruptReason::Cancelled
+    );
+  }
+
+  #[test]
+  fn running_out_of_input_pauses() {
+    // Echoes two inputs
+    let mut program = Program::from(vec![3, 9, 4, 9, 3, 9, 4, 9, 99, 0]);
+    let result = program.run_with_limits(Some(vec![5]), &RunLimits::default());
+    let interrupted = interrupted(result.unwrap());
+
+    assert_eq!(interrupted.reason, InterruptReason::AwaitingInput);
+    assert_eq!(interrupted.outputs, vec![5]);
+    assert_eq!(interrupted.state.pointer, 4);
+    let mut resumed = interrupted.state;
+    assert_eq!(resumed.run(Some(vec![6])).unwrap(), vec![6]);
+    assert!(Program::from(vec![3, 0, 99]).run(None).is_err());
+  }
+
+  #[test]
+  fn halting_program_is_unaffected() {
+    let mut program = Program::from(vec![104, 7, 99]);
+    let limits = RunLimits::default().with_max_steps(1);
+    match program.run_with_limits(None, &limits).unwrap() {
+      RunResult::Halted(outputs) => assert_eq!(outputs, vec![7]),
+      RunResult::Interrupted(_) => panic!("Program was interrupted"),
+    }
+  }
+}
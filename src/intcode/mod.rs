@@ -1,4 +1,11 @@
+pub mod compiler;
+pub mod decompiler;
 pub mod instruction;
+pub mod limits;
 pub mod opcode;
 pub mod parameter;
 pub mod program;
+pub mod registry;
+
+#[cfg(test)]
+mod fuzz;
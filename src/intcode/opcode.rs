@@ -1,6 +1,8 @@
-use crate::util::{digit_count, digits};
+use std::fmt;
 
-#[derive(Debug, PartialEq)]
+use crate::{util::digit, Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Opcode {
   Add,
   Multiply,
@@ -12,22 +14,44 @@ pub enum Opcode {
   Equals,
   AdjustRelativeBase,
   Halt,
+  /// Opcode registered in an `OpcodeRegistry`. Bit `i` of `write_mask` is set if parameter `i` is
+  /// written to.
+  Custom {
+    code: usize,
+    name: &'static str,
+    parameter_count: usize,
+    write_mask: u8,
+  },
 }
 
-impl From<usize> for Opcode {
-  fn from(value: usize) -> Opcode {
+/// Built-in opcodes by their variant name, custom ones by their registered name and code, e.g.
+/// `debug (50)`.
+impl fmt::Display for Opcode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Opcode::Custom { code, name, .. } => write!(f, "{} ({})", name, code),
+      builtin => write!(f, "{:?}", builtin),
+    }
+  }
+}
+
+/// Built-in opcodes only, custom ones are decoded by `OpcodeRegistry::decode`.
+impl TryFrom<usize> for Opcode {
+  type Error = Error;
+
+  fn try_from(value: usize) -> Result<Opcode> {
     match value {
-      1 => Opcode::Add,
-      2 => Opcode::Multiply,
-      3 => Opcode::SaveInput,
-      4 => Opcode::Output,
-      5 => Opcode::JumpIfTrue,
-      6 => Opcode::JumpIfFalse,
-      7 => Opcode::LessThan,
-      8 => Opcode::Equals,
-      9 => Opcode::AdjustRelativeBase,
-      99 => Opcode::Halt,
-      _ => panic!("Invalid opcode: {}", value),
+      1 => Ok(Opcode::Add),
+      2 => Ok(Opcode::Multiply),
+      3 => Ok(Opcode::SaveInput),
+      4 => Ok(Opcode::Output),
+      5 => Ok(Opcode::JumpIfTrue),
+      6 => Ok(Opcode::JumpIfFalse),
+      7 => Ok(Opcode::LessThan),
+      8 => Ok(Opcode::Equals),
+      9 => Ok(Opcode::AdjustRelativeBase),
+      99 => Ok(Opcode::Halt),
+      _ => Err(format!("Invalid opcode: {}", value).into()),
     }
   }
 }
@@ -45,18 +69,32 @@ impl Opcode {
       Opcode::Equals => 3,
       Opcode::AdjustRelativeBase => 1,
       Opcode::Halt => 0,
+      Opcode::Custom {
+        parameter_count, ..
+      } => *parameter_count,
     }
   }
 
-  pub fn from_first_value(value: usize) -> Opcode {
-    // Backwards compatibility for day 2 intcode implementation
-    if digit_count(value as isize) <= 2 {
-      return Opcode::from(value);
+  pub fn write_mask(&self) -> u8 {
+    match self {
+      Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 0b100,
+      Opcode::SaveInput => 0b1,
+      Opcode::Custom { write_mask, .. } => *write_mask,
+      _ => 0,
     }
+  }
+
+  pub fn is_builtin(code: usize) -> bool {
+    matches!(code, 1..=9 | 99)
+  }
+
+  /// The two-digit opcode number of an instruction's first value, without parameter modes.
+  pub fn code_from_first_value(value: isize) -> Result<usize> {
+    let value = usize::try_from(value).map_err(|_| format!("Invalid opcode: {}", value))?;
+    Ok(digit(value, 0, 10) + 10 * digit(value, 1, 10))
+  }
 
-    let first_value_digits: Vec<usize> = digits(value);
-    let opcode_num =
-      first_value_digits.last().unwrap() + 10 * first_value_digits[first_value_digits.len() - 2];
-    Opcode::from(opcode_num)
+  pub fn from_first_value(value: isize) -> Result<Opcode> {
+    Opcode::try_from(Opcode::code_from_first_value(value)?)
   }
 }
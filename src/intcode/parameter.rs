@@ -1,4 +1,4 @@
-use crate::util::{digit_count, digits};
+use crate::{util::digit, Context, Error, Result};
 
 use super::program::Program;
 
@@ -9,31 +9,24 @@ pub enum ParameterMode {
   Relative,
 }
 
-impl From<usize> for ParameterMode {
-  fn from(value: usize) -> ParameterMode {
+impl TryFrom<usize> for ParameterMode {
+  type Error = Error;
+
+  fn try_from(value: usize) -> Result<ParameterMode> {
     match value {
-      0 => ParameterMode::Position,
-      1 => ParameterMode::Immediate,
-      2 => ParameterMode::Relative,
-      _ => panic!("Invalid parameter mode: {}", value),
+      0 => Ok(ParameterMode::Position),
+      1 => Ok(ParameterMode::Immediate),
+      2 => Ok(ParameterMode::Relative),
+      _ => Err(format!("Invalid parameter mode: {}", value).into()),
     }
   }
 }
 
 impl ParameterMode {
-  pub fn from_first_value(value: usize, index: usize) -> ParameterMode {
-    // Backwards compatibility for day 2 intcode implementation
-    if digit_count(value as isize) <= 2 {
-      return ParameterMode::Position;
-    }
-
-    let first_value_digits: Vec<usize> = digits(value);
-    let param_mode_index: isize = first_value_digits.len() as isize - 3 - index as isize;
-
-    if param_mode_index < 0 {
-      return ParameterMode::Position;
-    }
-    ParameterMode::from(first_value_digits[param_mode_index as usize])
+  pub fn from_first_value(value: isize, index: usize) -> Result<ParameterMode> {
+    let value = usize::try_from(value).map_err(|_| format!("Invalid opcode: {}", value))?;
+    // Modes start after the two opcode digits, with missing ones meaning position mode
+    ParameterMode::try_from(digit(value, 2 + index as u32, 10))
   }
 }
 
@@ -44,24 +37,23 @@ pub struct Parameter {
 }
 
 impl Parameter {
-  pub fn address(&self, program: &Program) -> usize {
-    match self.mode {
-      // Position and relative parameters are always addresses (and thus positive)
-      ParameterMode::Position => self.address_or_value as usize,
-      ParameterMode::Immediate => panic!("Cannot get address of immediate parameter"),
-      ParameterMode::Relative => (program.relative_base + self.address_or_value) as usize,
-    }
+  /// The address a position or relative parameter refers to.
+  pub fn address(&self, program: &Program) -> Result<usize> {
+    let address = match self.mode {
+      ParameterMode::Position => self.address_or_value,
+      ParameterMode::Immediate => return Err("Immediate parameters have no address".into()),
+      ParameterMode::Relative => program
+        .relative_base
+        .checked_add(self.address_or_value)
+        .context("Relative address overflows")?,
+    };
+    usize::try_from(address).map_err(|_| format!("Invalid address: {}", address).into())
   }
 
-  pub fn get_value(&self, program: &Program) -> isize {
+  pub fn get_value(&self, program: &Program) -> Result<isize> {
     match self.mode {
-      // Position and relative parameters are always addresses (and thus positive)
-      ParameterMode::Position => program.get(self.address_or_value as usize),
-      ParameterMode::Immediate => self.address_or_value,
-      ParameterMode::Relative => {
-        let address = program.relative_base + self.address_or_value;
-        program.get(address as usize)
-      }
+      ParameterMode::Immediate => Ok(self.address_or_value),
+      _ => program.get(self.address(program)?),
     }
   }
 }
@@ -1,10 +1,12 @@
-use std::{fs, path::PathBuf};
+use std::{fs, path::PathBuf, str::FromStr, sync::Arc, time::Instant};
 
-use crate::Result;
+use crate::{util::parse::Span, Context, Error, Result};
 
 use super::{
-  instruction::{Instruction, InstructionResult},
+  instruction::{Instruction, InstructionIo, InstructionResult},
+  limits::{InterruptReason, Interrupted, RunLimits, RunResult},
   opcode::Opcode,
+  registry::OpcodeRegistry,
 };
 
 const MEMORY_SIZE: usize = 2000;
@@ -14,21 +16,21 @@ pub struct Program {
   pub memory: Vec<isize>,
   pub pointer: usize,
   pub relative_base: isize,
+  pub opcodes: Arc<OpcodeRegistry>,
 }
 
-impl From<&PathBuf> for Program {
-  fn from(path: &PathBuf) -> Program {
-    let initial = fs::read_to_string(path).unwrap();
-    // Allocate minimum memory size
-    let mut memory: Vec<isize> = Vec::with_capacity(MEMORY_SIZE);
-
-    memory.extend(
-      initial
-        .trim()
-        .split(',')
-        .map(|s| s.parse::<isize>().unwrap()),
-    );
+impl FromStr for Program {
+  type Err = Error;
+
+  /// Parses comma separated memory values.
+  fn from_str(source: &str) -> Result<Program> {
+    let memory: Vec<isize> = Span::new(source).trim().separated(",")?;
+    Ok(Program::from(memory))
+  }
+}
 
+impl From<Vec<isize>> for Program {
+  fn from(mut memory: Vec<isize>) -> Program {
     // Expand memory to minimum size if needed
     if memory.len() < MEMORY_SIZE {
       memory.resize(MEMORY_SIZE, 0);
@@ -38,67 +40,180 @@ impl From<&PathBuf> for Program {
       memory,
       pointer: 0,
       relative_base: 0,
+      opcodes: Arc::new(OpcodeRegistry::default()),
     }
   }
 }
 
 impl Program {
-  pub fn get(&self, address: usize) -> isize {
-    self.memory[address]
+  pub fn from_file(path: &PathBuf) -> Result<Program> {
+    fs::read_to_string(path)?.parse()
+  }
+
+  /// Makes the opcodes in `registry` available to the program, in addition to the built-in ones.
+  pub fn with_opcodes(mut self, registry: OpcodeRegistry) -> Program {
+    self.opcodes = Arc::new(registry);
+    self
+  }
+
+  pub fn get(&self, address: usize) -> Result<isize> {
+    self
+      .memory
+      .get(address)
+      .copied()
+      .with_context(|| format!("Address {} is outside memory", address))
   }
 
-  pub fn set(&mut self, address: usize, value: isize) {
-    self.memory[address] = value;
+  pub fn set(&mut self, address: usize, value: isize) -> Result<()> {
+    let cell = self
+      .memory
+      .get_mut(address)
+      .with_context(|| format!("Address {} is outside memory", address))?;
+    *cell = value;
+    Ok(())
   }
 
-  pub fn get_current(&self) -> isize {
+  pub fn get_current(&self) -> Result<isize> {
     self.get(self.pointer)
   }
 
-  pub fn get_instruction(&self, param_count: usize) -> Instruction {
-    Instruction::from(&self.memory[self.pointer..=self.pointer + param_count])
+  pub fn current_opcode(&self) -> Result<Opcode> {
+    self
+      .get_current()
+      .and_then(|value| self.opcodes.decode(value))
+      .with_context(|| format!("Instruction at {}", self.pointer))
+  }
+
+  pub fn get_instruction(&self, opcode: Opcode) -> Result<Instruction> {
+    let end = self.pointer + opcode.parameter_count();
+    let ints = self
+      .memory
+      .get(self.pointer..=end)
+      .context("Parameters run past the end of memory");
+    ints
+      .and_then(|ints| Instruction::new(opcode, ints))
+      .with_context(|| format!("{} at {}", opcode, self.pointer))
   }
 
   pub fn run_instruction(
     &mut self,
     instruction: &Instruction,
-    input: Option<isize>,
-  ) -> InstructionResult {
-    let result: InstructionResult = instruction.run(self, input);
+    io: &mut InstructionIo,
+  ) -> Result<InstructionResult> {
+    let pointer = self.pointer;
+    let result: InstructionResult = instruction
+      .run(self, io)
+      .with_context(|| format!("{} at {}", instruction.opcode, pointer))?;
     self.pointer = result
       .pointer
-      .unwrap_or(self.pointer + instruction.opcode.parameter_count() + 1);
-    result
+      .unwrap_or(pointer + instruction.opcode.parameter_count() + 1);
+    Ok(result)
   }
 
-  pub fn adjust_relative_base(&mut self, value: isize) {
-    self.relative_base += value;
+  pub fn adjust_relative_base(&mut self, value: isize) -> Result<()> {
+    self.relative_base = self
+      .relative_base
+      .checked_add(value)
+      .context("Relative base overflows")?;
+    Ok(())
   }
 
   pub fn run(&mut self, inputs: Option<Vec<isize>>) -> Result<Vec<isize>> {
+    match self.run_with_limits(inputs, &RunLimits::default())? {
+      RunResult::Halted(outputs) => Ok(outputs),
+      RunResult::Interrupted(interrupted) => match interrupted.reason {
+        InterruptReason::AwaitingInput => {
+          let pointer = interrupted.state.pointer;
+          Err(format!("SaveInput at {}: Out of input", pointer).into())
+        }
+        reason => Err(format!("Unlimited run was interrupted: {:?}", reason).into()),
+      },
+    }
+  }
+
+  /// Runs until the program halts, one of the limits is reached or it needs more input than
+  /// `inputs`. When it stops early, the result carries a copy of the machine positioned at the next
+  /// instruction.
+  pub fn run_with_limits(
+    &mut self,
+    inputs: Option<Vec<isize>>,
+    limits: &RunLimits,
+  ) -> Result<RunResult> {
+    let inputs = inputs.unwrap_or_default();
+    let started = Instant::now();
+    let mut steps = 0;
     let mut input_pointer = 0;
     let mut outputs: Vec<isize> = vec![];
     loop {
-      let opcode = Opcode::from_first_value(self.get_current() as usize);
+      let opcode = self.current_opcode()?;
       if opcode == Opcode::Halt {
         break;
       }
 
-      let instruction = self.get_instruction(opcode.parameter_count());
-      let unwrapped_inputs = inputs.clone().unwrap_or(vec![]);
+      let reason = if opcode == Opcode::SaveInput && input_pointer >= inputs.len() {
+        Some(InterruptReason::AwaitingInput)
+      } else {
+        limits.exceeded(steps, started)
+      };
+      if let Some(reason) = reason {
+        return Ok(RunResult::Interrupted(Interrupted {
+          reason,
+          state: self.clone(),
+          outputs,
+          steps,
+        }));
+      }
 
-      let result: InstructionResult =
-        self.run_instruction(&instruction, unwrapped_inputs.get(input_pointer).copied());
+      let instruction = self.get_instruction(opcode)?;
+      let mut io = InstructionIo::new(&inputs[input_pointer..], &outputs);
+      let result: InstructionResult = self.run_instruction(&instruction, &mut io)?;
+      input_pointer += io.read();
 
       if let Some(output) = result.output {
         outputs.push(output);
       }
-
-      if instruction.opcode == Opcode::SaveInput {
-        input_pointer += 1;
-      }
+      steps += 1;
     }
 
-    Ok(outputs)
+    Ok(RunResult::Halted(outputs))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn fault(memory: Vec<isize>) -> String {
+    Program::from(memory)
+      .run(Some(vec![1]))
+      .unwrap_err()
+      .to_string()
+  }
+
+  #[test]
+  fn faults_are_errors_with_their_instruction() {
+    assert_eq!(fault(vec![77]), "Instruction at 0: Invalid opcode: 77");
+    assert_eq!(
+      fault(vec![1101, 1, 1, 7, 401, 0]),
+      "Add at 4: Invalid parameter mode: 4"
+    );
+    assert_eq!(fault(vec![1, 0, -3, 0]), "Add at 0: Invalid address: -3");
+    assert_eq!(
+      fault(vec![11101, 1, 1, 0, 99]),
+      "Add at 0: Immediate parameters have no address"
+    );
+    assert_eq!(
+      fault(vec![1105, 1, -1]),
+      "JumpIfTrue at 0: Invalid jump address: -1"
+    );
+    assert_eq!(
+      fault(vec![4, 5000]),
+      "Output at 0: Address 5000 is outside memory"
+    );
+    assert_eq!(
+      fault(vec![109, 4000, 204, 1000]),
+      "Output at 2: Address 5000 is outside memory"
+    );
+    assert_eq!(fault(vec![3, 9, 3, 9, 99]), "SaveInput at 2: Out of input");
   }
 }
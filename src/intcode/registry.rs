@@ -0,0 +1,186 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use crate::Result;
+
+use super::{
+  instruction::{InstructionIo, InstructionResult},
+  opcode::Opcode,
+  program::Program,
+};
+
+/// Receives the program, one argument per parameter (the address for written parameters, the
+/// value for the rest) and the run's I/O to read inputs from.
+pub type OpcodeHandler =
+  dyn Fn(&mut Program, &[isize], &mut InstructionIo) -> Result<InstructionResult> + Send + Sync;
+
+pub struct CustomOpcode {
+  pub name: &'static str,
+  pub parameter_count: usize,
+  pub write_mask: u8,
+  pub handler: Arc<OpcodeHandler>,
+}
+
+/// Extra opcodes for experimental Intcode dialects, such as debug prints or host calls.
+/// Built-in opcodes cannot be overridden.
+#[derive(Default)]
+pub struct OpcodeRegistry {
+  custom: HashMap<usize, CustomOpcode>,
+}
+
+impl fmt::Debug for OpcodeRegistry {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut codes: Vec<(&usize, &str)> = self
+      .custom
+      .iter()
+      .map(|(code, custom)| (code, custom.name))
+      .collect();
+    codes.sort();
+    f.debug_map().entries(codes).finish()
+  }
+}
+
+impl OpcodeRegistry {
+  pub fn new() -> OpcodeRegistry {
+    OpcodeRegistry::default()
+  }
+
+  pub fn register<F>(
+    &mut self,
+    code: usize,
+    name: &'static str,
+    parameter_count: usize,
+    write_mask: u8,
+    handler: F,
+  ) -> Result<()>
+  where
+    F: Fn(&mut Program, &[isize], &mut InstructionIo) -> Result<InstructionResult>
+      + Send
+      + Sync
+      + 'static,
+  {
+    if code == 0 || code > 99 {
+      return Err(format!("Opcode {} does not fit in two digits", code).into());
+    }
+    if Opcode::is_builtin(code) {
+      return Err(format!("Opcode {} is built in and cannot be replaced", code).into());
+    }
+    if let Some(existing) = self.custom.get(&code) {
+      return Err(format!("Opcode {} is already registered as {}", code, existing.name).into());
+    }
+    if parameter_count > 8 || write_mask as usize >> parameter_count != 0 {
+      return Err(
+        format!(
+          "Write mask {:#b} of {} does not match its parameters",
+          write_mask, name
+        )
+        .into(),
+      );
+    }
+
+    self.custom.insert(
+      code,
+      CustomOpcode {
+        name,
+        parameter_count,
+        write_mask,
+        handler: Arc::new(handler),
+      },
+    );
+    Ok(())
+  }
+
+  pub fn get(&self, code: usize) -> Option<&CustomOpcode> {
+    self.custom.get(&code)
+  }
+
+  pub fn decode(&self, first_value: isize) -> Result<Opcode> {
+    let code = Opcode::code_from_first_value(first_value)?;
+    match self.custom.get(&code) {
+      Some(custom) => Ok(Opcode::Custom {
+        code,
+        name: custom.name,
+        parameter_count: custom.parameter_count,
+        write_mask: custom.write_mask,
+      }),
+      None => Opcode::try_from(code),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Mutex;
+
+  use super::*;
+  use crate::Context;
+
+  #[test]
+  fn custom_opcodes_run_alongside_builtins() {
+    let printed = Arc::new(Mutex::new(vec![]));
+    let log = Arc::clone(&printed);
+
+    let mut registry = OpcodeRegistry::new();
+    // Debug print: records its argument without producing program output
+    registry
+      .register(50, "debug", 1, 0, move |_, args, _| {
+        log.lock().unwrap().push(args[0]);
+        Ok(InstructionResult::empty())
+      })
+      .unwrap();
+    // Host call: squares the first parameter into the address of the second
+    registry
+      .register(51, "square", 2, 0b10, |program, args, _| {
+        program.set(args[1] as usize, args[0] * args[0])?;
+        Ok(InstructionResult::empty())
+      })
+      .unwrap();
+
+    let memory = vec![150, 7, 51, 11, 12, 4, 12, 50, 12, 99, 0, 9, 0];
+    let mut program = Program::from(memory).with_opcodes(registry);
+
+    assert_eq!(program.run(None).unwrap(), vec![81]);
+    assert_eq!(*printed.lock().unwrap(), vec![7, 81]);
+  }
+
+  #[test]
+  fn handlers_read_inputs() {
+    let mut registry = OpcodeRegistry::new();
+    // Host call: outputs the sum of its parameter and the next two inputs, once per output so far
+    registry
+      .register(52, "sum_inputs", 1, 0, |_, args, io| {
+        let a = io.input().context("Out of input")?;
+        let b = io.input().context("Out of input")?;
+        Ok(InstructionResult::from_output(
+          args[0] + a + b + io.outputs.len() as isize,
+        ))
+      })
+      .unwrap();
+
+    let memory = vec![3, 100, 52, 100, 152, 5, 3, 100, 4, 100, 99];
+    let mut program = Program::from(memory).with_opcodes(registry);
+    let opcodes = Arc::clone(&program.opcodes);
+    assert_eq!(
+      program.run(Some(vec![1, 10, 20, 30, 40, 7])).unwrap(),
+      vec![31, 76, 7]
+    );
+
+    let mut program = Program::from(vec![152, 5, 99]);
+    program.opcodes = opcodes;
+    assert_eq!(
+      program.run(Some(vec![1])).unwrap_err().to_string(),
+      "sum_inputs (52) at 0: Out of input"
+    );
+  }
+
+  #[test]
+  fn builtin_and_duplicate_opcodes_are_rejected() {
+    let mut registry = OpcodeRegistry::new();
+    let noop = |_: &mut Program, _: &[isize], _: &mut InstructionIo| Ok(InstructionResult::empty());
+
+    assert!(registry.register(1, "add", 3, 0b100, noop).is_err());
+    assert!(registry.register(100, "wide", 0, 0, noop).is_err());
+    assert!(registry.register(20, "mask", 1, 0b10, noop).is_err());
+    assert!(registry.register(20, "noop", 0, 0, noop).is_ok());
+    assert!(registry.register(20, "noop", 0, 0, noop).is_err());
+  }
+}
@@ -0,0 +1,8 @@
+//! The Intcode computer and utilities shared by the Advent of Code solutions, which live in the
+//! binary and use this crate like any other client.
+
+pub mod error;
+pub mod intcode;
+pub mod util;
+
+pub use error::{Context, Error, Result};
@@ -1,57 +1,396 @@
 use crate::{
-  days::{day_10, day_2, day_3, day_4, day_5, day_6, day_7, day_8, day_9},
-  intcode::program::Program,
+  bench::{bench_day, Timing},
+  cli::{Command, Format, Options, USAGE},
+  inputs::Input,
+  report::Record,
+  runner::{run_days, solve, DayResult},
+  solution::Part,
+  verify::{check_example, Answers},
 };
-use std::path::PathBuf;
+use advent_of_code::intcode::{decompiler, program::Program};
+use std::{env, fs, path::PathBuf, process, thread, time::Duration};
 
-mod days;
-mod intcode;
-mod util;
+mod bench;
+mod cli;
+mod inputs;
+mod report;
+mod runner;
+mod scaffold;
+mod solution;
+mod verify;
+mod watch;
+mod years;
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+pub use advent_of_code::{Context, Error, Result};
+
+/// A day's input: `--input` if given and the one in the input directory otherwise.
+fn day_input(options: &Options, day: u8) -> Result<Input> {
+  match &options.input {
+    Some(input) => Ok(input.clone()),
+    None => options.inputs.resolve(day),
+  }
+}
+
+/// Solves the selected days on `--jobs` threads, returning them in day order.
+fn solve_days(options: &Options, parts: &[Part]) -> Vec<DayResult> {
+  run_days(&options.days, options.jobs, |day| {
+    let solution = years::solution(options.year, day).expect("Selected days are solved");
+    let input = day_input(options, day)?;
+    solve(solution, &input.read()?, parts).context(&input)
+  })
+}
+
+/// Prints each selected day's answers, returning whether all of them succeeded.
+fn run(options: &Options, parts: &[Part]) -> bool {
+  let json = options.format == Format::Json;
+  let mut succeeded = true;
+  let mut records: Vec<Record> = vec![];
+  for DayResult {
+    day,
+    results,
+    duration,
+  } in solve_days(options, parts)
+  {
+    if !json {
+      println!("Day {} ({:.2?})", day, duration);
+    }
+    let results = match results {
+      Ok(results) => results,
+      Err(error) => {
+        succeeded = false;
+        if json {
+          let error = Err(format!("input: {}", error).into());
+          records.extend(
+            parts
+              .iter()
+              .map(|&part| Record::new(options.year, day, part, &error, None)),
+          );
+        } else {
+          eprintln!("Day {} input failed: {}", day, error);
+        }
+        continue;
+      }
+    };
+    for result in results {
+      succeeded &= result.answer.is_ok();
+      if json {
+        records.push(Record::new(
+          options.year,
+          day,
+          result.part,
+          &result.answer,
+          Some(result.duration),
+        ));
+        continue;
+      }
+      let part = result.part.number();
+      match result.answer {
+        // Multi-line answers such as images start on their own line
+        Ok(answer) if answer.contains('\n') => println!("  Part {}:\n{}", part, answer),
+        Ok(answer) => println!("  Part {}: {}", part, answer),
+        Err(error) => eprintln!("Day {} part {} failed: {}", day, part, error),
+      }
+    }
+  }
+
+  if json {
+    println!("{}", report::to_json(&records));
+  }
+  succeeded
+}
+
+/// Compares each selected day's answers with the confirmed ones, returning whether none of them
+/// failed. Parts without a confirmed answer are reported as unknown.
+fn verify(options: &Options, parts: &[Part]) -> bool {
+  let path = options
+    .answers
+    .clone()
+    .unwrap_or_else(|| options.inputs.answers());
+  let confirmed = match Answers::from_file(&path) {
+    Ok(confirmed) => confirmed,
+    Err(error) => {
+      eprintln!("Answers file {}: {}", path.display(), error);
+      return false;
+    }
+  };
+
+  let json = options.format == Format::Json;
+  let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+  let mut records: Vec<Record> = vec![];
+  for DayResult { day, results, .. } in solve_days(options, parts) {
+    let results: Vec<(Part, Result<String>, Option<Duration>)> = match results {
+      Ok(results) => results
+        .into_iter()
+        .map(|result| (result.part, result.answer, Some(result.duration)))
+        .collect(),
+      Err(error) => parts
+        .iter()
+        .map(|&part| (part, Err(format!("input: {}", error).into()), None))
+        .collect(),
+    };
+
+    for (part, answer, duration) in results {
+      let outcome = confirmed.check(day, part, &answer);
+      if json {
+        records.push(Record::from_outcome(
+          options.year,
+          day,
+          part,
+          &answer,
+          &outcome,
+          duration,
+        ));
+      } else {
+        println!("Day {} part {}: {}", day, part.number(), outcome);
+      }
+      match outcome {
+        _ if outcome.is_failure() => failed += 1,
+        verify::Outcome::Unknown(_) => unknown += 1,
+        _ => passed += 1,
+      }
+    }
+  }
+
+  if json {
+    println!("{}", report::to_json(&records));
+  } else {
+    println!(
+      "\n{} passed, {} failed, {} unknown",
+      passed, failed, unknown
+    );
+  }
+  failed == 0
+}
+
+/// Checks each selected day's published examples, returning whether all of them passed.
+fn examples(options: &Options, parts: &[Part]) -> bool {
+  let (mut passed, mut failed) = (0, 0);
+  for &day in &options.days {
+    let solution = years::solution(options.year, day).expect("Selected days are solved");
+    let selected = solution
+      .examples()
+      .iter()
+      .enumerate()
+      .filter(|(_, example)| parts.contains(&example.part));
+    for (i, example) in selected {
+      let outcome = check_example(solution, example);
+      println!(
+        "Day {} part {} example {}: {}",
+        day,
+        example.part.number(),
+        i + 1,
+        outcome
+      );
+      match outcome {
+        verify::Outcome::Pass => passed += 1,
+        _ => failed += 1,
+      }
+    }
+  }
+
+  println!("\n{} passed, {} failed", passed, failed);
+  failed == 0
+}
+
+/// Creates the selected year's next day, returning whether that succeeded.
+fn new_day(year: u16, day: u8) -> bool {
+  let last_day = *years::days(year)
+    .expect("Years are checked when parsed")
+    .end();
+  match scaffold::new_day(&scaffold::default_root(), year, day, last_day) {
+    Ok(created) => {
+      for path in created {
+        println!("Created {}", path.display());
+      }
+      println!("Registered day {} in src/years/year_{}/mod.rs", day, year);
+      true
+    }
+    Err(error) => {
+      eprintln!("Can't create day {}: {}", day, error);
+      false
+    }
+  }
+}
+
+/// Prints the Intcode program in `path` as pseudocode.
+fn decompile(path: &PathBuf) -> bool {
+  match Program::from_file(path) {
+    Ok(program) => {
+      print!("{}", decompiler::decompile(&program.memory));
+      true
+    }
+    Err(error) => {
+      eprintln!("Can't decompile {}: {}", path.display(), error);
+      false
+    }
+  }
+}
+
+/// Re-runs the selected day on its inputs whenever one of them changes, until interrupted. Only
+/// inputs are watched: the day's source is compiled into this binary, so changing it needs a
+/// rebuild and a new watch.
+fn watch(options: &Options, parts: &[Part]) -> bool {
+  let day = options.days[0];
+  let solution = years::solution(options.year, day).expect("Selected days are solved");
+  let mut last_seen = vec![];
+  let mut previous = watch::Answers::new();
+  loop {
+    let inputs = match watch::watched_inputs(day, options.input.as_ref(), &options.inputs) {
+      Ok(inputs) => inputs,
+      Err(error) => {
+        eprintln!("Can't watch day {}: {}", day, error);
+        return false;
+      }
+    };
+
+    let seen = watch::snapshot(&inputs);
+    if seen != last_seen {
+      let names: Vec<String> = inputs
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+      println!("Day {} on {}", day, names.join(", "));
+      let answers = watch::run_inputs(solution, &inputs, parts);
+      for line in watch::describe(&answers, &previous) {
+        println!("  {}", line);
+      }
+      println!();
+      previous = answers;
+      last_seen = seen;
+    }
+    thread::sleep(options.interval);
+  }
+}
+
+/// Times each selected day, writing the summary and comparing with the baseline if requested.
+/// Returns whether every day ran and no median regressed past the threshold.
+fn bench(options: &Options, parts: &[Part]) -> bool {
+  let settings = &options.bench;
+  let baseline = match &settings.baseline {
+    Some(path) => match fs::read_to_string(path)
+      .map_err(Error::from)
+      .and_then(|csv| bench::parse_baseline(&csv))
+    {
+      Ok(baseline) => Some(baseline),
+      Err(error) => {
+        eprintln!("Baseline {}: {}", path.display(), error);
+        return false;
+      }
+    },
+    None => None,
+  };
+
+  let json = options.format == Format::Json;
+  let mut succeeded = true;
+  let mut timings: Vec<Timing> = vec![];
+  if !json {
+    println!(
+      "{:<5}{:<8}{:>12}{:>12}{:>12}{:>10}",
+      "Day", "Step", "Min", "Median", "Mean", "Change"
+    );
+  }
+  for &day in &options.days {
+    let solution = years::solution(options.year, day).expect("Selected days are solved");
+    let day_timings = match day_input(options, day).and_then(|input| {
+      bench_day(
+        options.year,
+        day,
+        solution,
+        &input.read()?,
+        parts,
+        settings.runs,
+      )
+      .context(&input)
+    }) {
+      Ok(day_timings) => day_timings,
+      Err(error) => {
+        eprintln!("Day {} failed: {}", day, error);
+        succeeded = false;
+        continue;
+      }
+    };
+
+    for timing in day_timings {
+      let change = baseline
+        .as_ref()
+        .and_then(|baseline| bench::change(&timing, baseline));
+      let regressed = change.is_some_and(|change| change > settings.threshold);
+      succeeded &= !regressed;
+      if regressed && json {
+        eprintln!(
+          "Day {} {} regressed by {:.1}%",
+          timing.day,
+          timing.step,
+          change.unwrap_or_default()
+        );
+      }
+      if json {
+        timings.push(timing);
+        continue;
+      }
+      println!(
+        "{:<5}{:<8}{:>12}{:>12}{:>12}{:>10}{}",
+        timing.day,
+        timing.step.to_string(),
+        format!("{:.2?}", timing.min),
+        format!("{:.2?}", timing.median),
+        format!("{:.2?}", timing.mean),
+        change.map_or(String::new(), |change| format!("{:+.1}%", change)),
+        if regressed { "  REGRESSION" } else { "" }
+      );
+      timings.push(timing);
+    }
+  }
+
+  if json {
+    print!("{}", bench::to_json(&timings));
+  }
+  if let Some(path) = &settings.output {
+    if let Err(error) = bench::write_summary(path, &timings) {
+      eprintln!("Summary {}: {}", path.display(), error);
+      succeeded = false;
+    }
+  }
+  succeeded
+}
 
 fn main() {
-  println!(
-    "Total rocket fuel requirement: {}",
-    days::day_1::total_fuel_requirement().unwrap()
-  );
-
-  let mut program = Program::from(&PathBuf::from("src/input/day_2.txt"));
-  day_2::restore_intcode_program(&mut program);
-  program.run(None).unwrap();
-  println!(
-    "Value at position 0 after running intcode: {}",
-    program.memory[0]
-  );
-
-  let (noun, verb) = day_2::find_noun_verb().unwrap();
-  println!(
-    "Noun and verb that produce the target output {}: {}",
-    day_2::TARGET_OUTPUT,
-    100 * noun + verb
-  );
-
-  let intersection = day_3::closest_wire_intersection().unwrap();
-  println!(
-    "Closest intersection distance: {}",
-    day_3::manhattan_distance(intersection.0, intersection.1)
-  );
-
-  let lowest_steps = day_3::lowest_steps_to_intersection().unwrap();
-  println!("Lowest steps to intersection: {}", lowest_steps);
-
-  println!(
-    "Amount of possible passwords in range {} - {}: {}",
-    day_4::LOWER_LIMIT,
-    day_4::UPPER_LIMIT,
-    day_4::possible_passwords_count()
-  );
-
-  day_5::run_day_5();
-  day_6::run_day_6();
-  day_7::run_day_7();
-  day_8::run_day_8();
-  day_9::run_day_9();
-  day_10::run_day_10();
+  let options = match Options::parse(env::args().skip(1)) {
+    Ok(options) => options,
+    Err(error) => {
+      eprintln!("{}\n\n{}", error, USAGE);
+      process::exit(2);
+    }
+  };
+  if options.help {
+    println!("{}", USAGE);
+    return;
+  }
+
+  runner::report_panics();
+  let parts = match options.part {
+    Some(part) => vec![part],
+    None => Part::BOTH.to_vec(),
+  };
+  let succeeded = match options.command {
+    Command::Run => run(&options, &parts),
+    Command::Verify => verify(&options, &parts),
+    Command::Bench => bench(&options, &parts),
+    Command::Examples => examples(&options, &parts),
+    Command::Watch => watch(&options, &parts),
+    Command::New => new_day(
+      options.year,
+      options.new_day.expect("The day to create was parsed"),
+    ),
+    Command::Decompile => decompile(
+      options
+        .program
+        .as_ref()
+        .expect("The program to decompile was parsed"),
+    ),
+  };
+
+  if !succeeded {
+    process::exit(1);
+  }
 }
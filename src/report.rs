@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use crate::{solution::Part, verify::Outcome};
+
+/// Quotes `value` as a JSON string.
+pub fn json_string(value: &str) -> String {
+  let mut quoted = String::from('"');
+  for c in value.chars() {
+    match c {
+      '"' => quoted.push_str("\\\""),
+      '\\' => quoted.push_str("\\\\"),
+      '\n' => quoted.push_str("\\n"),
+      '\r' => quoted.push_str("\\r"),
+      '\t' => quoted.push_str("\\t"),
+      c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+      c => quoted.push(c),
+    }
+  }
+  quoted.push('"');
+  quoted
+}
+
+/// A part's result, as printed by `--format json`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Record {
+  pub year: u16,
+  pub day: u8,
+  pub part: u8,
+  /// `ok` or `error` when running, the outcome's name when verifying.
+  pub status: &'static str,
+  pub answer: Option<String>,
+  pub expected: Option<String>,
+  pub error: Option<String>,
+  /// `None` if the part didn't run because its input failed.
+  pub duration: Option<Duration>,
+}
+
+impl Record {
+  pub fn new(
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &crate::Result<String>,
+    duration: Option<Duration>,
+  ) -> Record {
+    let (status, answer, error) = match answer {
+      Ok(answer) => ("ok", Some(answer.clone()), None),
+      Err(error) => ("error", None, Some(error.to_string())),
+    };
+    Record {
+      year,
+      day,
+      part: part.number(),
+      status,
+      answer,
+      error,
+      duration,
+      ..Record::default()
+    }
+  }
+
+  /// The record of a verified part, whose status is the outcome's name.
+  pub fn from_outcome(
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &crate::Result<String>,
+    outcome: &Outcome,
+    duration: Option<Duration>,
+  ) -> Record {
+    let (status, expected) = match outcome {
+      Outcome::Pass => ("pass", None),
+      Outcome::Fail { expected, .. } => ("fail", Some(expected.clone())),
+      Outcome::Unknown(_) => ("unknown", None),
+      Outcome::Error(_) => ("error", None),
+    };
+    Record {
+      status,
+      expected,
+      ..Record::new(year, day, part, answer, duration)
+    }
+  }
+
+  /// A single line object. Multi-line answers, such as rendered images, also get their lines as
+  /// an array.
+  pub fn to_json(&self) -> String {
+    let mut fields = vec![
+      format!("\"year\": {}", self.year),
+      format!("\"day\": {}", self.day),
+      format!("\"part\": {}", self.part),
+      format!("\"status\": {}", json_string(self.status)),
+    ];
+    if let Some(answer) = &self.answer {
+      fields.push(format!("\"answer\": {}", json_string(answer)));
+      if answer.contains('\n') {
+        let lines: Vec<String> = answer.lines().map(json_string).collect();
+        fields.push(format!("\"lines\": [{}]", lines.join(", ")));
+      }
+    }
+    if let Some(expected) = &self.expected {
+      fields.push(format!("\"expected\": {}", json_string(expected)));
+    }
+    if let Some(error) = &self.error {
+      fields.push(format!("\"error\": {}", json_string(error)));
+    }
+    match self.duration {
+      Some(duration) => fields.push(format!("\"duration_ns\": {}", duration.as_nanos())),
+      None => fields.push("\"duration_ns\": null".to_string()),
+    }
+    format!("{{{}}}", fields.join(", "))
+  }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+  let objects: Vec<String> = records
+    .iter()
+    .map(|record| format!("  {}", record.to_json()))
+    .collect();
+  format!("[\n{}\n]", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn writes_records() {
+    assert_eq!(
+      json_string("a \"b\"\\\n\u{1}"),
+      "\"a \\\"b\\\"\\\\\\n\\u0001\""
+    );
+
+    let record = Record::new(
+      2019,
+      8,
+      Part::Two,
+      &Ok(" █\n█ ".to_string()),
+      Some(Duration::from_nanos(7)),
+    );
+    assert_eq!(
+      record.to_json(),
+      "{\"year\": 2019, \"day\": 8, \"part\": 2, \"status\": \"ok\", \"answer\": \" █\\n█ \", \
+       \"lines\": [\" █\", \"█ \"], \"duration_ns\": 7}"
+    );
+
+    let record = Record::new(2019, 3, Part::One, &Err("input: missing".into()), None);
+    assert_eq!(
+      record.to_json(),
+      "{\"year\": 2019, \"day\": 3, \"part\": 1, \"status\": \"error\", \"error\": \"input: missing\", \
+       \"duration_ns\": null}"
+    );
+
+    let outcome = Outcome::Fail {
+      expected: "159".to_string(),
+      actual: "160".to_string(),
+    };
+    let record = Record::from_outcome(
+      2019,
+      3,
+      Part::One,
+      &Ok("160".to_string()),
+      &outcome,
+      Some(Duration::from_nanos(1)),
+    );
+    assert_eq!(
+      to_json(&[record]),
+      "[\n  {\"year\": 2019, \"day\": 3, \"part\": 1, \"status\": \"fail\", \"answer\": \"160\", \
+       \"expected\": \"159\", \"duration_ns\": 1}\n]"
+    );
+  }
+}
@@ -0,0 +1,166 @@
+use std::{
+  any::Any,
+  cell::Cell,
+  panic::{self, AssertUnwindSafe, Location},
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+  },
+  thread,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  solution::{DynSolution, Part},
+  Result,
+};
+
+thread_local! {
+  /// How many `catch` calls are running on this thread.
+  static CATCHING: Cell<usize> = const { Cell::new(0) };
+  /// Where the last caught panic on this thread happened, recorded by the hook `report_panics`
+  /// installs.
+  static PANIC_LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+/// Keeps panics in days from being printed, as they are reported as the failed part's or day's
+/// error along with their location. Other panics are printed as usual.
+pub fn report_panics() {
+  let default = panic::take_hook();
+  panic::set_hook(Box::new(move |info| {
+    if CATCHING.get() == 0 {
+      default(info);
+    } else {
+      PANIC_LOCATION.set(info.location().map(Location::to_string));
+    }
+  }));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+  match payload.downcast_ref::<&str>() {
+    Some(message) => message,
+    None => payload
+      .downcast_ref::<String>()
+      .map_or("no message", String::as_str),
+  }
+}
+
+/// Runs `task`, turning a panic into an error with its message.
+pub fn catch<T>(task: impl FnOnce() -> Result<T>) -> Result<T> {
+  CATCHING.set(CATCHING.get() + 1);
+  let result = panic::catch_unwind(AssertUnwindSafe(task));
+  CATCHING.set(CATCHING.get() - 1);
+
+  result.unwrap_or_else(|payload| {
+    let location = PANIC_LOCATION
+      .take()
+      .map_or(String::new(), |location| format!(" at {}", location));
+    Err(format!("panicked{}: {}", location, panic_message(payload.as_ref())).into())
+  })
+}
+
+pub struct PartResult {
+  pub part: Part,
+  pub answer: Result<String>,
+  pub duration: Duration,
+}
+
+/// Parses a day's input and runs the selected parts on it. Fails only if the input can't be
+/// parsed, each part's own failure is part of its result. Panics are reported as errors.
+pub fn solve(solution: &dyn DynSolution, input: &str, parts: &[Part]) -> Result<Vec<PartResult>> {
+  let parsed = catch(|| solution.parse(input))?;
+
+  Ok(
+    parts
+      .iter()
+      .map(|&part| {
+        let start = Instant::now();
+        // A panicking part is reported like an error so the remaining parts and days still run
+        let answer = catch(|| solution.part(part, &parsed));
+        PartResult {
+          part,
+          answer,
+          duration: start.elapsed(),
+        }
+      })
+      .collect(),
+  )
+}
+
+pub struct DayResult {
+  pub day: u8,
+  /// Fails if the input couldn't be read or parsed.
+  pub results: Result<Vec<PartResult>>,
+  pub duration: Duration,
+}
+
+/// Runs `task` for each day on up to `jobs` threads and returns the results in the order of
+/// `days`. A panicking task fails its day only.
+pub fn run_days<F>(days: &[u8], jobs: usize, task: F) -> Vec<DayResult>
+where
+  F: Fn(u8) -> Result<Vec<PartResult>> + Sync,
+{
+  let next = AtomicUsize::new(0);
+  let finished: Mutex<Vec<Option<DayResult>>> = Mutex::new(days.iter().map(|_| None).collect());
+
+  thread::scope(|scope| {
+    for _ in 0..jobs.clamp(1, days.len().max(1)) {
+      scope.spawn(|| loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(&day) = days.get(index) else {
+          break;
+        };
+
+        let start = Instant::now();
+        let results = catch(|| task(day));
+        let result = DayResult {
+          day,
+          results,
+          duration: start.elapsed(),
+        };
+        finished.lock().expect("No worker panics holding the lock")[index] = Some(result);
+      });
+    }
+  });
+
+  finished
+    .into_inner()
+    .expect("No worker panics holding the lock")
+    .into_iter()
+    .map(|result| result.expect("Every day was run"))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn collates_days_in_order() {
+    let days = [1, 2, 3, 4, 5];
+    let results = run_days(&days, 3, |day| {
+      // Later days finish first
+      thread::sleep(Duration::from_millis(10 * (6 - day as u64)));
+      match day {
+        2 => Err("no input".into()),
+        4 => panic!("day 4"),
+        _ => Ok(vec![PartResult {
+          part: Part::One,
+          answer: Ok(day.to_string()),
+          duration: Duration::ZERO,
+        }]),
+      }
+    });
+
+    assert_eq!(
+      results.iter().map(|result| result.day).collect::<Vec<u8>>(),
+      days
+    );
+    let answer = |result: &DayResult| match &result.results {
+      Ok(parts) => parts[0].answer.as_ref().unwrap().clone(),
+      Err(error) => error.to_string(),
+    };
+    let answers: Vec<String> = results.iter().map(answer).collect();
+    assert_eq!(answers, ["1", "no input", "3", "panicked: day 4", "5"]);
+  }
+}
@@ -0,0 +1,208 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use crate::Result;
+
+/// The crate's own sources, where new days are added.
+pub fn default_root() -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn template(day: u8) -> String {
+  format!(
+    "\
+use crate::{{
+  solution::{{Example, Part, Solution}},
+  Result,
+}};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+  type Input = String;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  // Replace with the puzzle's examples, the day's example test fails until then
+  const EXAMPLES: &'static [Example] = &[Example {{
+    part: Part::One,
+    input: \"\",
+    answer: \"TODO\",
+  }}];
+
+  fn parse(&self, input: &str) -> Result<String> {{
+    Ok(input.trim().to_string())
+  }}
+
+  fn part_one(&self, _input: &String) -> Result<usize> {{
+    Err(\"Part one is not solved yet\".into())
+  }}
+
+  fn part_two(&self, _input: &String) -> Result<usize> {{
+    Err(\"Part two is not solved yet\".into())
+  }}
+}}
+"
+  )
+}
+
+/// Inserts `line` after the last line matching `after`, failing if there's none.
+fn insert_after(
+  source: &mut Vec<String>,
+  after: impl Fn(&str) -> bool,
+  line: String,
+) -> Result<()> {
+  let index = source
+    .iter()
+    .rposition(|existing| after(existing))
+    .ok_or_else(|| {
+      format!(
+        "Can't find where to add `{}` in the year's mod.rs",
+        line.trim()
+      )
+    })?;
+  source.insert(index + 1, line);
+  Ok(())
+}
+
+/// Adds the day to its year's registry in `years/year_N/mod.rs`: its module, `DAYS`, `solution`
+/// and the example tests.
+fn register(registry: &str, day: u8) -> Result<String> {
+  let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+  let previous = day - 1;
+
+  let days = format!("pub const DAYS: RangeInclusive<u8> = 1..={};", previous);
+  let days_line = lines
+    .iter_mut()
+    .find(|line| **line == days)
+    .ok_or_else(|| format!("Can't find `{}` in the year's mod.rs", days))?;
+  *days_line = format!("pub const DAYS: RangeInclusive<u8> = 1..={};", day);
+
+  insert_after(
+    &mut lines,
+    |line| line.starts_with("pub mod day_"),
+    format!("pub mod day_{};", day),
+  )?;
+  let solution = format!("{} => Some(&day_{}::Day{}),", previous, previous, previous);
+  insert_after(
+    &mut lines,
+    |line| line.trim() == solution,
+    format!("    {} => Some(&day_{}::Day{}),", day, day, day),
+  )?;
+  let examples = format!("day_{}_examples: {},", previous, previous);
+  insert_after(
+    &mut lines,
+    |line| line.trim() == examples,
+    format!("    day_{}_examples: {},", day, day),
+  )?;
+
+  // Keep the module declarations in rustfmt's order
+  let first = lines
+    .iter()
+    .position(|line| line.starts_with("pub mod day_"));
+  let count = lines
+    .iter()
+    .filter(|line| line.starts_with("pub mod day_"))
+    .count();
+  if let Some(first) = first {
+    lines[first..first + count]
+      .sort_by(|a, b| a.trim_end_matches(';').cmp(b.trim_end_matches(';')));
+  }
+
+  Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the module, input and sample input for the day after the year's last one, registers it
+/// and returns the created files. Nothing is written if any of them already exists.
+pub fn new_day(root: &Path, year: u16, day: u8, last_day: u8) -> Result<Vec<PathBuf>> {
+  if day <= last_day {
+    return Err(format!("Day {} already exists", day).into());
+  }
+  if day != last_day + 1 {
+    return Err(format!("Days are added in order, the next one is {}", last_day + 1).into());
+  }
+
+  let days = root.join(format!("src/years/year_{}", year));
+  let inputs = root.join(format!("src/input/{}", year));
+  let module = days.join(format!("day_{}.rs", day));
+  let input = inputs.join(format!("day_{}.txt", day));
+  let sample = inputs.join(format!("day_{}.sample.txt", day));
+  let created = vec![module.clone(), input.clone(), sample.clone()];
+  for path in created.iter().chain([&days.join(format!("day_{}", day))]) {
+    if path.exists() {
+      return Err(format!("Day {} already exists: {}", day, path.display()).into());
+    }
+  }
+
+  let registry_path = days.join("mod.rs");
+  let registry = register(&fs::read_to_string(&registry_path)?, day)?;
+
+  fs::create_dir_all(&inputs)?;
+  fs::write(&module, template(day))?;
+  fs::write(&input, "")?;
+  fs::write(&sample, "")?;
+  fs::write(&registry_path, registry)?;
+  Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::env;
+
+  use super::*;
+
+  const REGISTRY: &str = "\
+pub mod day_1;
+pub mod day_2;
+pub mod day_9;
+
+pub const DAYS: RangeInclusive<u8> = 1..=9;
+
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+  match day {
+    1 => Some(&day_1::Day1),
+    9 => Some(&day_9::Day9),
+    _ => None,
+  }
+}
+
+  example_tests! {
+    day_1_examples: 1,
+    day_9_examples: 9,
+  }
+";
+
+  #[test]
+  fn registers_day() {
+    let registry = register(REGISTRY, 10).unwrap();
+    assert!(registry.starts_with("pub mod day_1;\npub mod day_10;\npub mod day_2;\n"));
+    assert!(registry.contains("1..=10;"));
+    assert!(registry.contains("    9 => Some(&day_9::Day9),\n    10 => Some(&day_10::Day10),\n"));
+    assert!(registry.contains("    day_9_examples: 9,\n    day_10_examples: 10,\n"));
+
+    assert!(register(REGISTRY, 11).is_err());
+  }
+
+  #[test]
+  fn refuses_existing_days() {
+    let root = env::temp_dir().join(format!("scaffold_{}", std::process::id()));
+    fs::create_dir_all(root.join("src/years/year_2019")).unwrap();
+    fs::write(root.join("src/years/year_2019/mod.rs"), REGISTRY).unwrap();
+
+    let created = new_day(&root, 2019, 10, 9).unwrap();
+    assert_eq!(created[1], root.join("src/input/2019/day_10.txt"));
+    assert_eq!(created.len(), 3);
+    assert!(fs::read_to_string(&created[0])
+      .unwrap()
+      .contains("impl Solution for Day10 {"));
+    assert!(new_day(&root, 2019, 10, 9)
+      .unwrap_err()
+      .to_string()
+      .starts_with("Day 10 already exists"));
+    assert!(new_day(&root, 2019, 12, 10).is_err());
+
+    fs::remove_dir_all(root).unwrap();
+  }
+}
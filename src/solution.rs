@@ -0,0 +1,75 @@
+use std::{any::Any, fmt::Display};
+
+use crate::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+  pub fn number(&self) -> u8 {
+    match self {
+      Part::One => 1,
+      Part::Two => 2,
+    }
+  }
+}
+
+/// One of a puzzle's published examples: running `part` on `input` answers `answer`.
+pub struct Example {
+  pub part: Part,
+  pub input: &'static str,
+  pub answer: &'static str,
+}
+
+/// A day's puzzle. The input is parsed once and shared by both parts, which return their answers
+/// rather than printing them.
+pub trait Solution {
+  type Input;
+  type PartOne: Display;
+  type PartTwo: Display;
+
+  const EXAMPLES: &'static [Example] = &[];
+
+  fn parse(&self, input: &str) -> Result<Self::Input>;
+  fn part_one(&self, input: &Self::Input) -> Result<Self::PartOne>;
+  fn part_two(&self, input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+pub type ParsedInput = Box<dyn Any + Send>;
+
+/// `Solution` with its types erased, so days can be stored together in the registry.
+pub trait DynSolution: Sync {
+  fn parse(&self, input: &str) -> Result<ParsedInput>;
+  /// Runs a part on input returned by this solution's `parse`.
+  fn part(&self, part: Part, input: &ParsedInput) -> Result<String>;
+  fn examples(&self) -> &'static [Example];
+}
+
+impl<S> DynSolution for S
+where
+  S: Solution + Sync,
+  S::Input: Send + 'static,
+{
+  fn parse(&self, input: &str) -> Result<ParsedInput> {
+    Ok(Box::new(Solution::parse(self, input)?))
+  }
+
+  fn part(&self, part: Part, input: &ParsedInput) -> Result<String> {
+    let input = input
+      .downcast_ref::<S::Input>()
+      .ok_or("Input was parsed by a different solution")?;
+    match part {
+      Part::One => Ok(self.part_one(input)?.to_string()),
+      Part::Two => Ok(self.part_two(input)?.to_string()),
+    }
+  }
+
+  fn examples(&self) -> &'static [Example] {
+    S::EXAMPLES
+  }
+}
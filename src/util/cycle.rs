@@ -0,0 +1,144 @@
+//! Finding when a simulation starts repeating, so a state far in the future can be reached from
+//! one in the first pass. Neither search ends if the states never repeat.
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::util::math;
+
+/// The states after `start` steps repeat every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+  pub start: usize,
+  pub length: usize,
+}
+
+impl Cycle {
+  /// The cycle of several parts changing independently, such as the axes of a simulation: they all
+  /// repeat once the last of them has, and together once every length has passed.
+  pub fn combine(cycles: impl IntoIterator<Item = Cycle>) -> Cycle {
+    let (mut start, mut length) = (0, 1);
+    for cycle in cycles {
+      start = start.max(cycle.start);
+      length = math::lcm(length, cycle.length as i128);
+    }
+    Cycle {
+      start,
+      length: length as usize,
+    }
+  }
+
+  /// The step, before the states first repeat, whose state is the same as after `steps` steps.
+  pub fn equivalent_step(&self, steps: usize) -> usize {
+    if steps < self.start {
+      steps
+    } else {
+      self.start + (steps - self.start) % self.length
+    }
+  }
+}
+
+/// Brent's algorithm, which only keeps two states at a time but steps through the states a few
+/// times over.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+  // Finds the length by leaving the tortoise at each power of two until the hare catches it
+  let (mut power, mut length) = (1, 1);
+  let mut tortoise = start.clone();
+  let mut hare = step(&start);
+  while tortoise != hare {
+    if power == length {
+      tortoise = hare.clone();
+      power *= 2;
+      length = 0;
+    }
+    hare = step(&hare);
+    length += 1;
+  }
+
+  // With the hare a cycle ahead, they meet where the cycle starts
+  let mut tortoise = start.clone();
+  let mut hare = start;
+  for _ in 0..length {
+    hare = step(&hare);
+  }
+  let mut cycle_start = 0;
+  while tortoise != hare {
+    tortoise = step(&tortoise);
+    hare = step(&hare);
+    cycle_start += 1;
+  }
+
+  Cycle {
+    start: cycle_start,
+    length,
+  }
+}
+
+/// Remembers every state to step through them only once, until the first repeat.
+pub fn find_cycle<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+  let mut seen = HashMap::new();
+  let mut state = start;
+  for steps in 0.. {
+    let next = step(&state);
+    if let Some(first) = seen.insert(state, steps) {
+      return Cycle {
+        start: first,
+        length: steps - first,
+      };
+    }
+    state = next;
+  }
+  unreachable!("ran out of steps")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn finds_where_states_repeat() {
+    // 0, 1, 2, 3, 4, then 5 to 11 over and over
+    let step = |&n: &u32| if n == 11 { 5 } else { n + 1 };
+    let cycle = Cycle {
+      start: 5,
+      length: 7,
+    };
+    assert_eq!(brent(0, step), cycle);
+    assert_eq!(find_cycle(0, step), cycle);
+    assert_eq!(brent(7, step).start, 0);
+    assert_eq!(find_cycle(7, step), brent(7, step));
+    let fixed = find_cycle(5, |&n: &u32| n);
+    assert_eq!((fixed.start, fixed.length), (0, 1));
+
+    assert_eq!(cycle.equivalent_step(3), 3);
+    assert_eq!(cycle.equivalent_step(12), 5);
+    assert_eq!(cycle.equivalent_step(1_000_000), 5 + (1_000_000 - 5) % 7);
+  }
+
+  /// Counts up from 0, going back to `lead` after `length` values from it.
+  fn advance(n: usize, (lead, length): (usize, usize)) -> usize {
+    if n + 1 == lead + length {
+      lead
+    } else {
+      n + 1
+    }
+  }
+
+  #[test]
+  fn combines_independent_cycles() {
+    const AXES: [(usize, usize); 3] = [(3, 4), (0, 6), (1, 10)];
+
+    // Each axis moves on its own, so they can be searched separately
+    let axes = AXES.map(|axis| brent(0, move |&n| advance(n, axis)));
+    let combined = Cycle::combine(axes);
+    assert_eq!((combined.start, combined.length), (3, 60));
+
+    let whole = find_cycle([0; 3], |state| {
+      let mut next = *state;
+      for (n, axis) in next.iter_mut().zip(AXES) {
+        *n = advance(*n, axis);
+      }
+      next
+    });
+    assert_eq!(whole, combined);
+  }
+}
@@ -0,0 +1,398 @@
+//! Points and directions on grids and in space. `y` grows downwards, as it does in puzzle maps, so
+//! up is `(0, -1)` and turning clockwise from up faces right.
+
+use crate::util::math;
+
+use std::{
+  cmp::Ordering,
+  fmt::Debug,
+  hash::Hash,
+  ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+};
+
+/// A signed integer usable as a coordinate.
+pub trait Coordinate:
+  Copy
+  + Debug
+  + Default
+  + Ord
+  + Hash
+  + Add<Output = Self>
+  + Sub<Output = Self>
+  + Mul<Output = Self>
+  + Div<Output = Self>
+  + Rem<Output = Self>
+  + Neg<Output = Self>
+{
+  const ZERO: Self;
+  const ONE: Self;
+
+  fn abs(self) -> Self;
+  /// The greatest common divisor of both absolute values, `0` only if both are.
+  fn gcd(self, other: Self) -> Self;
+}
+
+macro_rules! coordinate {
+  ($($type:ty)*) => {
+    $(
+      impl Coordinate for $type {
+        const ZERO: $type = 0;
+        const ONE: $type = 1;
+
+        fn abs(self) -> $type {
+          <$type>::abs(self)
+        }
+
+        fn gcd(self, other: $type) -> $type {
+          math::gcd(self as i128, other as i128) as $type
+        }
+      }
+    )*
+  };
+}
+
+coordinate!(i8 i16 i32 i64 i128 isize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = isize> {
+  pub x: T,
+  pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T = isize> {
+  pub x: T,
+  pub y: T,
+  pub z: T,
+}
+
+/// Component-wise `+`, `-` and negation between points, and scaling by a coordinate.
+macro_rules! operators {
+  ($point:ident { $($field:ident),* }) => {
+    impl<T: Coordinate> Add for $point<T> {
+      type Output = $point<T>;
+
+      fn add(self, other: $point<T>) -> $point<T> {
+        $point { $($field: self.$field + other.$field),* }
+      }
+    }
+
+    impl<T: Coordinate> Sub for $point<T> {
+      type Output = $point<T>;
+
+      fn sub(self, other: $point<T>) -> $point<T> {
+        $point { $($field: self.$field - other.$field),* }
+      }
+    }
+
+    impl<T: Coordinate> Neg for $point<T> {
+      type Output = $point<T>;
+
+      fn neg(self) -> $point<T> {
+        $point { $($field: -self.$field),* }
+      }
+    }
+
+    impl<T: Coordinate> Mul<T> for $point<T> {
+      type Output = $point<T>;
+
+      fn mul(self, factor: T) -> $point<T> {
+        $point { $($field: self.$field * factor),* }
+      }
+    }
+
+    impl<T: Coordinate> Div<T> for $point<T> {
+      type Output = $point<T>;
+
+      fn div(self, divisor: T) -> $point<T> {
+        $point { $($field: self.$field / divisor),* }
+      }
+    }
+
+    impl<T: Coordinate> AddAssign for $point<T> {
+      fn add_assign(&mut self, other: $point<T>) {
+        *self = *self + other;
+      }
+    }
+
+    impl<T: Coordinate> SubAssign for $point<T> {
+      fn sub_assign(&mut self, other: $point<T>) {
+        *self = *self - other;
+      }
+    }
+
+    impl<T: Coordinate> MulAssign<T> for $point<T> {
+      fn mul_assign(&mut self, factor: T) {
+        *self = *self * factor;
+      }
+    }
+
+    impl<T: Coordinate> DivAssign<T> for $point<T> {
+      fn div_assign(&mut self, divisor: T) {
+        *self = *self / divisor;
+      }
+    }
+
+    impl<T: Coordinate> $point<T> {
+      pub const ORIGIN: $point<T> = $point { $($field: T::ZERO),* };
+
+      /// The sum of the distances along each axis.
+      pub fn manhattan(self, other: $point<T>) -> T {
+        T::ZERO $(+ (self.$field - other.$field).abs())*
+      }
+
+      /// The largest distance along an axis, the moves a king needs on a chessboard.
+      pub fn chebyshev(self, other: $point<T>) -> T {
+        T::ZERO $(.max((self.$field - other.$field).abs()))*
+      }
+    }
+  };
+}
+
+operators!(Point { x, y });
+operators!(Point3 { x, y, z });
+
+impl<T> Point<T> {
+  pub const fn new(x: T, y: T) -> Point<T> {
+    Point { x, y }
+  }
+}
+
+impl<T> Point3<T> {
+  pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+    Point3 { x, y, z }
+  }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+  fn from((x, y): (T, T)) -> Point<T> {
+    Point { x, y }
+  }
+}
+
+impl<T: Coordinate> Point<T> {
+  /// The smallest vector with the same direction, whose multiples reach every point on the way.
+  /// The origin stays as it is.
+  pub fn reduced(self) -> Point<T> {
+    match self.x.gcd(self.y) {
+      divisor if divisor == T::ZERO => self,
+      divisor => self / divisor,
+    }
+  }
+
+  /// Orders vectors by their direction clockwise from up, without rounding errors. Vectors with
+  /// the same direction are equal, whatever their length.
+  pub fn clockwise_cmp(&self, other: &Point<T>) -> Ordering {
+    // Up to just before down is the first half turn, the rest is the second
+    let half = |point: &Point<T>| !(point.x > T::ZERO || (point.x == T::ZERO && point.y < T::ZERO));
+    half(self).cmp(&half(other)).then_with(|| {
+      // Positive when `other` is clockwise of `self`, with y growing downwards
+      let cross = self.x * other.y - self.y * other.x;
+      T::ZERO.cmp(&cross)
+    })
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+  Up,
+  Right,
+  Down,
+  Left,
+}
+
+impl Direction {
+  /// Clockwise from up.
+  pub const ALL: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+  ];
+
+  fn turned(self, quarters: usize) -> Direction {
+    Direction::ALL[(self as usize + quarters) % 4]
+  }
+
+  pub fn turn_right(self) -> Direction {
+    self.turned(1)
+  }
+
+  pub fn reverse(self) -> Direction {
+    self.turned(2)
+  }
+
+  pub fn turn_left(self) -> Direction {
+    self.turned(3)
+  }
+
+  /// The step one move in this direction makes.
+  pub fn offset<T: Coordinate>(self) -> Point<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+    match self {
+      Direction::Up => Point::new(zero, -one),
+      Direction::Right => Point::new(one, zero),
+      Direction::Down => Point::new(zero, one),
+      Direction::Left => Point::new(-one, zero),
+    }
+  }
+}
+
+/// `U`, `R`, `D` or `L`.
+impl TryFrom<char> for Direction {
+  type Error = crate::Error;
+
+  fn try_from(c: char) -> crate::Result<Direction> {
+    match c {
+      'U' => Ok(Direction::Up),
+      'R' => Ok(Direction::Right),
+      'D' => Ok(Direction::Down),
+      'L' => Ok(Direction::Left),
+      _ => Err(format!("Invalid direction: {}", c).into()),
+    }
+  }
+}
+
+/// The eight directions of a compass, diagonals included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+  North,
+  NorthEast,
+  East,
+  SouthEast,
+  South,
+  SouthWest,
+  West,
+  NorthWest,
+}
+
+impl Direction8 {
+  /// Clockwise from north.
+  pub const ALL: [Direction8; 8] = [
+    Direction8::North,
+    Direction8::NorthEast,
+    Direction8::East,
+    Direction8::SouthEast,
+    Direction8::South,
+    Direction8::SouthWest,
+    Direction8::West,
+    Direction8::NorthWest,
+  ];
+
+  fn turned(self, eighths: usize) -> Direction8 {
+    Direction8::ALL[(self as usize + eighths) % 8]
+  }
+
+  /// Turns clockwise by 45 degrees.
+  pub fn turn_right(self) -> Direction8 {
+    self.turned(1)
+  }
+
+  pub fn reverse(self) -> Direction8 {
+    self.turned(4)
+  }
+
+  /// Turns counterclockwise by 45 degrees.
+  pub fn turn_left(self) -> Direction8 {
+    self.turned(7)
+  }
+
+  pub fn offset<T: Coordinate>(self) -> Point<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+    let (x, y) = match self {
+      Direction8::North => (zero, -one),
+      Direction8::NorthEast => (one, -one),
+      Direction8::East => (one, zero),
+      Direction8::SouthEast => (one, one),
+      Direction8::South => (zero, one),
+      Direction8::SouthWest => (-one, one),
+      Direction8::West => (-one, zero),
+      Direction8::NorthWest => (-one, -one),
+    };
+    Point::new(x, y)
+  }
+}
+
+impl From<Direction> for Direction8 {
+  fn from(direction: Direction) -> Direction8 {
+    Direction8::ALL[direction as usize * 2]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn does_arithmetic_and_distances() {
+    let a = Point::new(3, -4);
+    let b = Point::new(-1, 2);
+    assert_eq!(a + b, Point::new(2, -2));
+    assert_eq!(a - b, Point::new(4, -6));
+    assert_eq!(-a * 2, Point::new(-6, 8));
+    assert_eq!(a.manhattan(b), 10);
+    assert_eq!(a.chebyshev(b), 6);
+    assert_eq!(a.manhattan(Point::ORIGIN), 7);
+
+    let mut c = Point3::new(1, 2, 3);
+    c += Point3::new(1, 1, 1);
+    c *= 3;
+    assert_eq!(c, Point3::new(6, 9, 12));
+    assert_eq!(c.manhattan(Point3::ORIGIN), 27);
+    assert_eq!(c.chebyshev(Point3::new(0, 0, 20)), 9);
+  }
+
+  #[test]
+  fn reduces_and_orders_by_angle() {
+    assert_eq!(Point::new(6, -4).reduced(), Point::new(3, -2));
+    assert_eq!(Point::new(0, 5).reduced(), Point::new(0, 1));
+    assert_eq!(Point::<i32>::ORIGIN.reduced(), Point::ORIGIN);
+
+    let mut vectors = [
+      Point::new(-1, -1),
+      Point::new(0, 1),
+      Point::new(1, 0),
+      Point::new(-1, 0),
+      Point::new(0, -1),
+      Point::new(2, 1),
+      Point::new(1, -1),
+    ];
+    vectors.sort_by(Point::clockwise_cmp);
+    assert_eq!(
+      vectors,
+      [
+        Point::new(0, -1),
+        Point::new(1, -1),
+        Point::new(1, 0),
+        Point::new(2, 1),
+        Point::new(0, 1),
+        Point::new(-1, 0),
+        Point::new(-1, -1),
+      ]
+    );
+    assert_eq!(
+      Point::new(1, 1).clockwise_cmp(&Point::new(3, 3)),
+      Ordering::Equal
+    );
+  }
+
+  #[test]
+  fn turns() {
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    assert_eq!(Direction::Left.reverse(), Direction::Right);
+    assert_eq!(Direction::Down.offset::<i32>(), Point::new(0, 1));
+    assert_eq!(Direction::try_from('L').unwrap(), Direction::Left);
+    assert!(Direction::try_from('X').is_err());
+
+    assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+    assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+    assert_eq!(Direction8::from(Direction::Down), Direction8::South);
+    let sum = Direction8::ALL
+      .iter()
+      .fold(Point::<i64>::ORIGIN, |sum, direction| {
+        sum + direction.offset()
+      });
+    assert_eq!(sum, Point::ORIGIN);
+  }
+}
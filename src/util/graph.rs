@@ -0,0 +1,297 @@
+//! Shortest path searches over any state that can be hashed, such as a grid's points or a maze
+//! position with the keys collected so far. The graph is never built, `neighbours` is called with
+//! each state the search reaches instead.
+
+use std::{
+  cmp::Reverse,
+  collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+  hash::Hash,
+  ops::Add,
+};
+
+/// The states a search reached, how much reaching each one cost and where it came from.
+pub struct Search<S, C> {
+  states: Vec<S>,
+  indices: HashMap<S, usize>,
+  costs: Vec<C>,
+  previous: Vec<Option<usize>>,
+  goal: Option<usize>,
+}
+
+impl<S, C> Search<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Copy,
+{
+  fn new() -> Search<S, C> {
+    Search {
+      states: vec![],
+      indices: HashMap::new(),
+      costs: vec![],
+      previous: vec![],
+      goal: None,
+    }
+  }
+
+  /// Records reaching `state` for `cost`, unless it was already reached for less. Returns the
+  /// state's index if this is the cheapest way to it so far.
+  fn reach(&mut self, state: S, cost: C, previous: Option<usize>) -> Option<usize>
+  where
+    C: Ord,
+  {
+    match self.indices.entry(state) {
+      Entry::Occupied(entry) => {
+        let index = *entry.get();
+        (cost < self.costs[index]).then(|| {
+          self.costs[index] = cost;
+          self.previous[index] = previous;
+          index
+        })
+      }
+      Entry::Vacant(entry) => {
+        let index = self.states.len();
+        self.states.push(entry.key().clone());
+        entry.insert(index);
+        self.costs.push(cost);
+        self.previous.push(previous);
+        Some(index)
+      }
+    }
+  }
+
+  /// The first goal state found, which no other goal is cheaper than.
+  pub fn goal(&self) -> Option<&S> {
+    self.goal.map(|index| &self.states[index])
+  }
+
+  pub fn goal_cost(&self) -> Option<C> {
+    self.goal.map(|index| self.costs[index])
+  }
+
+  /// The path from a start to the goal, both included.
+  pub fn goal_path(&self) -> Option<Vec<S>> {
+    self.goal.map(|index| self.path_to_index(index))
+  }
+
+  /// The cost of the cheapest way found to `state`, `None` if it wasn't reached.
+  pub fn cost(&self, state: &S) -> Option<C> {
+    self.indices.get(state).map(|&index| self.costs[index])
+  }
+
+  /// The cheapest path found from a start to `state`, both included.
+  pub fn path(&self, state: &S) -> Option<Vec<S>> {
+    self
+      .indices
+      .get(state)
+      .map(|&index| self.path_to_index(index))
+  }
+
+  fn path_to_index(&self, mut index: usize) -> Vec<S> {
+    let mut path = vec![self.states[index].clone()];
+    while let Some(previous) = self.previous[index] {
+      path.push(self.states[previous].clone());
+      index = previous;
+    }
+    path.reverse();
+    path
+  }
+
+  /// Every reached state with its cost.
+  pub fn costs(&self) -> impl Iterator<Item = (&S, C)> {
+    self.states.iter().zip(self.costs.iter().copied())
+  }
+}
+
+/// Searches breadth first from every start at once, so each state's cost is the fewest steps to
+/// it from any start. Stops at the first state `is_goal` accepts.
+pub fn bfs<S, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbours: impl FnMut(&S) -> I,
+  mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+  S: Clone + Eq + Hash,
+  I: IntoIterator<Item = S>,
+{
+  let mut search = Search::new();
+  let mut queue = VecDeque::new();
+  for start in starts {
+    queue.extend(search.reach(start, 0, None));
+  }
+
+  while let Some(index) = queue.pop_front() {
+    let state = search.states[index].clone();
+    if is_goal(&state) {
+      search.goal = Some(index);
+      break;
+    }
+    let steps = search.costs[index] + 1;
+    for neighbour in neighbours(&state) {
+      if !search.indices.contains_key(&neighbour) {
+        queue.extend(search.reach(neighbour, steps, Some(index)));
+      }
+    }
+  }
+  search
+}
+
+/// The fewest steps to every state reachable from the starts.
+pub fn flood_fill<S, I>(
+  starts: impl IntoIterator<Item = S>,
+  neighbours: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+  S: Clone + Eq + Hash,
+  I: IntoIterator<Item = S>,
+{
+  let search = bfs(starts, neighbours, |_| false);
+  search.states.into_iter().zip(search.costs).collect()
+}
+
+/// Searches for the cheapest path from any start, where `neighbours` gives each next state with
+/// the cost of moving to it. Costs must not be negative. Stops at the first state `is_goal`
+/// accepts.
+pub fn dijkstra<S, C, I>(
+  starts: impl IntoIterator<Item = S>,
+  neighbours: impl FnMut(&S) -> I,
+  is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Copy + Ord + Default + Add<Output = C>,
+  I: IntoIterator<Item = (S, C)>,
+{
+  astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Dijkstra's search guided by `heuristic`, an estimate of the cost left to reach a goal. The path
+/// found is the cheapest as long as the estimate never exceeds the actual cost.
+pub fn astar<S, C, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbours: impl FnMut(&S) -> I,
+  mut heuristic: impl FnMut(&S) -> C,
+  mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Copy + Ord + Default + Add<Output = C>,
+  I: IntoIterator<Item = (S, C)>,
+{
+  let mut search = Search::new();
+  let mut queue = BinaryHeap::new();
+  for start in starts {
+    let estimate = heuristic(&start);
+    if let Some(index) = search.reach(start, C::default(), None) {
+      queue.push(Reverse((estimate, C::default(), index)));
+    }
+  }
+
+  while let Some(Reverse((_, cost, index))) = queue.pop() {
+    // Skip states that were reached more cheaply after being queued
+    if cost > search.costs[index] {
+      continue;
+    }
+    let state = search.states[index].clone();
+    if is_goal(&state) {
+      search.goal = Some(index);
+      break;
+    }
+    for (neighbour, step) in neighbours(&state) {
+      let cost = cost + step;
+      let estimate = cost + heuristic(&neighbour);
+      if let Some(next) = search.reach(neighbour, cost, Some(index)) {
+        queue.push(Reverse((estimate, cost, next)));
+      }
+    }
+  }
+  search
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::util::{geometry::Point, grid::Grid};
+
+  const MAZE: &str = "\
+S.#.....
+.##.###.
+....#..E
+.##...#.";
+
+  fn maze() -> (Grid<char>, Point, Point) {
+    let grid = Grid::parse(MAZE, Some).unwrap();
+    let find = |target| {
+      grid
+        .iter()
+        .find(|(_, &c)| c == target)
+        .map(|(point, _)| point)
+        .unwrap()
+    };
+    let (start, end) = (find('S'), find('E'));
+    (grid, start, end)
+  }
+
+  #[test]
+  fn searches_grids() {
+    let (grid, start, end) = maze();
+    let open = |point: &Point| {
+      grid
+        .neighbours_4(*point)
+        .filter(|&next| grid.get(next) != Some(&'#'))
+        .collect::<Vec<Point>>()
+    };
+
+    let search = bfs([start], open, |&point| point == end);
+    assert_eq!(search.goal_cost(), Some(11));
+    let path = search.goal_path().unwrap();
+    assert_eq!((path[0], path[11]), (start, end));
+    assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+
+    let weighted = |point: &Point| open(point).into_iter().map(|next| (next, 1));
+    let search = dijkstra([start], weighted, |&point| point == end);
+    assert_eq!(search.goal_cost(), Some(11));
+    let search = astar(
+      [start],
+      weighted,
+      |point| point.manhattan(end),
+      |&point| point == end,
+    );
+    assert_eq!(search.goal(), Some(&end));
+    assert_eq!(search.goal_cost(), Some(11));
+
+    let distances = flood_fill([start, end], open);
+    assert_eq!(
+      distances.len(),
+      grid.iter().filter(|(_, &c)| c != '#').count()
+    );
+    assert_eq!(distances[&Point::new(4, 0)], 5);
+    assert_eq!(bfs([start], open, |_| false).goal(), None);
+  }
+
+  #[test]
+  fn finds_cheapest_paths() {
+    // Going through `b` takes more steps but costs less
+    let edges = HashMap::from([
+      ('a', vec![('b', 1), ('c', 10)]),
+      ('b', vec![('d', 1)]),
+      ('d', vec![('c', 1)]),
+      ('c', vec![('e', 1)]),
+    ]);
+    let neighbours = |state: &char| edges.get(state).cloned().unwrap_or_default();
+    let search = dijkstra(['a'], neighbours, |&state| state == 'e');
+    assert_eq!(search.goal_cost(), Some(4));
+    assert_eq!(search.goal_path().unwrap(), ['a', 'b', 'd', 'c', 'e']);
+    assert_eq!(search.cost(&'d'), Some(2));
+    assert_eq!(search.path(&'c').unwrap(), ['a', 'b', 'd', 'c']);
+    assert_eq!(search.costs().count(), 5);
+
+    // An implicit state space: reaching 10 by doubling or adding one
+    let next = |&n: &u32| {
+      [(n * 2, 1), (n + 1, 1)]
+        .into_iter()
+        .filter(|&(n, _)| n <= 10)
+    };
+    let search = dijkstra([1], next, |&n| n == 10);
+    assert_eq!(search.goal_path().unwrap(), [1, 2, 4, 5, 10]);
+  }
+}
@@ -0,0 +1,310 @@
+//! A rectangular grid of cells, such as a map or an image. The top left cell is at the origin, with
+//! `x` growing to the right and `y` growing downwards.
+
+use std::ops::{Index, IndexMut};
+
+use crate::{
+  util::{
+    geometry::{Direction, Direction8, Point},
+    parse::Span,
+  },
+  Result,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  /// Row by row from the top.
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  /// A grid of `cells` given row by row, failing if there aren't `width * height` of them.
+  pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Grid<T>> {
+    if cells.len() != width * height {
+      let error = format!(
+        "{} cells don't make a {}x{} grid",
+        cells.len(),
+        width,
+        height
+      );
+      return Err(error.into());
+    }
+    Ok(Grid {
+      width,
+      height,
+      cells,
+    })
+  }
+
+  pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+  where
+    T: Clone,
+  {
+    Grid {
+      width,
+      height,
+      cells: vec![value; width * height],
+    }
+  }
+
+  /// Parses a character grid, such as a `#.` map, with `cell` returning `None` for characters
+  /// that aren't valid cells.
+  pub fn parse(input: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>> {
+    let rows = Span::new(input).grid(cell)?;
+    let width = rows.first().map_or(0, Vec::len);
+    Ok(Grid {
+      width,
+      height: rows.len(),
+      cells: rows.into_iter().flatten().collect(),
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn contains(&self, point: Point) -> bool {
+    (0..self.width as isize).contains(&point.x) && (0..self.height as isize).contains(&point.y)
+  }
+
+  fn index_of(&self, point: Point) -> Option<usize> {
+    self
+      .contains(point)
+      .then(|| point.y as usize * self.width + point.x as usize)
+  }
+
+  /// The cell at `point`, or `None` outside the grid.
+  pub fn get(&self, point: Point) -> Option<&T> {
+    self.index_of(point).map(|index| &self.cells[index])
+  }
+
+  pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+    self.index_of(point).map(|index| &mut self.cells[index])
+  }
+
+  /// Every point, row by row from the top.
+  pub fn points(&self) -> impl Iterator<Item = Point> {
+    let width = self.width;
+    (0..self.cells.len())
+      .map(move |index| Point::new((index % width) as isize, (index / width) as isize))
+  }
+
+  /// Every cell with its point, row by row from the top.
+  pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+    self.points().zip(&self.cells)
+  }
+
+  /// The points above, right of, below and left of `point` that are in the grid.
+  pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+    self.in_grid(Direction::ALL.map(|direction| point + direction.offset()))
+  }
+
+  /// The points around `point`, diagonals included, that are in the grid.
+  pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+    self.in_grid(Direction8::ALL.map(|direction| point + direction.offset()))
+  }
+
+  fn in_grid<const N: usize>(&self, points: [Point; N]) -> impl Iterator<Item = Point> + '_ {
+    points.into_iter().filter(|&point| self.contains(point))
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    // `chunks` panics on 0, which only an empty grid can have
+    self.cells.chunks(self.width.max(1))
+  }
+
+  /// The cells with this `x`, from the top. Panics if it's outside the grid.
+  pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+    assert!(
+      x < self.width,
+      "Column {} is outside the {}x{} grid",
+      x,
+      self.width,
+      self.height
+    );
+    self.cells.iter().skip(x).step_by(self.width)
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(|x| self.column(x))
+  }
+
+  pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      cells: self.cells.iter().map(f).collect(),
+    }
+  }
+
+  /// A `width` by `height` grid whose cell at `(x, y)` is this grid's cell at `source(x, y)`.
+  fn rearranged(
+    &self,
+    width: usize,
+    height: usize,
+    source: impl Fn(usize, usize) -> (usize, usize),
+  ) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let cells = (0..height)
+      .flat_map(|y| (0..width).map(move |x| (x, y)))
+      .map(|(x, y)| self[source(x, y)].clone())
+      .collect();
+    Grid {
+      width,
+      height,
+      cells,
+    }
+  }
+
+  pub fn transpose(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    self.rearranged(self.height, self.width, |x, y| (y, x))
+  }
+
+  pub fn rotate_clockwise(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let last_row = self.height.saturating_sub(1);
+    self.rearranged(self.height, self.width, |x, y| (y, last_row - x))
+  }
+
+  pub fn rotate_counterclockwise(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let last_column = self.width.saturating_sub(1);
+    self.rearranged(self.height, self.width, |x, y| (last_column - y, x))
+  }
+
+  /// Mirrors the grid left to right.
+  pub fn flip_horizontal(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let last_column = self.width.saturating_sub(1);
+    self.rearranged(self.width, self.height, |x, y| (last_column - x, y))
+  }
+
+  /// Mirrors the grid top to bottom.
+  pub fn flip_vertical(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let last_row = self.height.saturating_sub(1);
+    self.rearranged(self.width, self.height, |x, y| (x, last_row - y))
+  }
+
+  /// The grid as text, a line per row with `cell` giving each cell's character.
+  pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+    let rows: Vec<String> = self
+      .rows()
+      .map(|row| row.iter().map(&cell).collect())
+      .collect();
+    rows.join("\n")
+  }
+}
+
+/// The cell at `(x, y)`, panicking outside the grid.
+impl<T> Index<(usize, usize)> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, (x, y): (usize, usize)) -> &T {
+    assert!(
+      x < self.width && y < self.height,
+      "({}, {}) is outside the {}x{} grid",
+      x,
+      y,
+      self.width,
+      self.height
+    );
+    &self.cells[y * self.width + x]
+  }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+  fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+    assert!(
+      x < self.width && y < self.height,
+      "({}, {}) is outside the {}x{} grid",
+      x,
+      y,
+      self.width,
+      self.height
+    );
+    &mut self.cells[y * self.width + x]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn digits(input: &str) -> Grid<u32> {
+    Grid::parse(input, |c| c.to_digit(10)).unwrap()
+  }
+
+  fn render(grid: &Grid<u32>) -> String {
+    grid.render(|&digit| char::from_digit(digit, 10).unwrap())
+  }
+
+  #[test]
+  fn indexes_and_finds_neighbours() {
+    let grid = digits("123\n456");
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 1)], 6);
+    assert_eq!(grid.get(Point::new(1, 0)), Some(&2));
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+
+    let neighbours: Vec<Point> = grid.neighbours_4(Point::ORIGIN).collect();
+    assert_eq!(neighbours, [Point::new(1, 0), Point::new(0, 1)]);
+    assert_eq!(grid.neighbours_8(Point::new(1, 0)).count(), 5);
+    assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &5)));
+
+    let rows: Vec<&[u32]> = grid.rows().collect();
+    assert_eq!(rows, [[1, 2, 3], [4, 5, 6]]);
+    let columns: Vec<Vec<u32>> = grid
+      .columns()
+      .map(|column| column.copied().collect())
+      .collect();
+    assert_eq!(columns, [[1, 4], [2, 5], [3, 6]]);
+
+    assert!(Grid::parse("12\n3", |c| c.to_digit(10)).is_err());
+    assert!(Grid::new(2, 2, vec![1, 2, 3]).is_err());
+  }
+
+  #[test]
+  #[should_panic(expected = "Column 3 is outside the 3x2 grid")]
+  fn rejects_columns_outside() {
+    digits("123\n456").column(3).next();
+  }
+
+  #[test]
+  fn transforms_and_renders() {
+    let grid = digits("123\n456");
+    assert_eq!(render(&grid), "123\n456");
+    assert_eq!(render(&grid.transpose()), "14\n25\n36");
+    assert_eq!(render(&grid.rotate_clockwise()), "41\n52\n63");
+    assert_eq!(render(&grid.rotate_counterclockwise()), "36\n25\n14");
+    assert_eq!(render(&grid.flip_horizontal()), "321\n654");
+    assert_eq!(render(&grid.flip_vertical()), "456\n123");
+    assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    assert_eq!(render(&grid.map(|digit| digit * 2 % 10)), "246\n802");
+
+    let mut filled = Grid::filled(2, 1, 0);
+    filled[(1, 0)] = 7;
+    *filled.get_mut(Point::ORIGIN).unwrap() = 3;
+    assert_eq!(render(&filled), "37");
+  }
+}
@@ -0,0 +1,187 @@
+//! Number theory for cycle lengths and modular arithmetic. Modular values are `i64`, with products
+//! taken on `i128` so they can't overflow for any modulus.
+
+/// The greatest common divisor of both absolute values, `0` only if both are.
+pub fn gcd(a: i128, b: i128) -> i128 {
+  let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+  while b != 0 {
+    (a, b) = (b, a % b);
+  }
+  a as i128
+}
+
+/// The smallest positive multiple of both, `0` if either is.
+pub fn lcm(a: i128, b: i128) -> i128 {
+  if a == 0 || b == 0 {
+    return 0;
+  }
+  (a / gcd(a, b) * b).abs()
+}
+
+/// The smallest positive multiple of every value, such as when cycles of these lengths all line up
+/// again. `1` for no values.
+pub fn lcm_all(values: impl IntoIterator<Item = i128>) -> i128 {
+  values.into_iter().fold(1, lcm)
+}
+
+/// `(g, x, y)` with `g` the greatest common divisor of `a` and `b`, and `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+  let (mut old_r, mut r) = (a, b);
+  let (mut old_x, mut x) = (1, 0);
+  let (mut old_y, mut y) = (0, 1);
+  while r != 0 {
+    let quotient = old_r / r;
+    (old_r, r) = (r, old_r - quotient * r);
+    (old_x, x) = (x, old_x - quotient * x);
+    (old_y, y) = (y, old_y - quotient * y);
+  }
+  if old_r < 0 {
+    (-old_r, -old_x, -old_y)
+  } else {
+    (old_r, old_x, old_y)
+  }
+}
+
+/// `(a * b) mod modulus`, between `0` and `modulus`.
+pub fn mul_mod(a: i64, b: i64, modulus: i64) -> i64 {
+  (a as i128 * b as i128).rem_euclid(modulus as i128) as i64
+}
+
+/// `base` to the power of `exponent`, mod `modulus`.
+pub fn pow_mod(base: i64, mut exponent: u64, modulus: i64) -> i64 {
+  let mut base = base.rem_euclid(modulus);
+  let mut result = 1 % modulus;
+  while exponent > 0 {
+    if exponent & 1 == 1 {
+      result = mul_mod(result, base, modulus);
+    }
+    base = mul_mod(base, base, modulus);
+    exponent >>= 1;
+  }
+  result
+}
+
+/// The `x` with `(a * x) mod modulus == 1`, which only exists if `a` and `modulus` are coprime.
+pub fn inverse_mod(a: i64, modulus: i64) -> Option<i64> {
+  let (g, x, _) = extended_gcd(a as i128, modulus as i128);
+  (g == 1).then(|| x.rem_euclid(modulus as i128) as i64)
+}
+
+/// The function `x ↦ (a * x + b) mod modulus`, such as where a card ends up after a shuffle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Linear {
+  pub a: i64,
+  pub b: i64,
+  pub modulus: i64,
+}
+
+impl Linear {
+  pub fn new(a: i64, b: i64, modulus: i64) -> Linear {
+    Linear {
+      a: a.rem_euclid(modulus),
+      b: b.rem_euclid(modulus),
+      modulus,
+    }
+  }
+
+  pub fn identity(modulus: i64) -> Linear {
+    Linear::new(1, 0, modulus)
+  }
+
+  pub fn apply(&self, x: i64) -> i64 {
+    let value = self.a as i128 * x as i128 + self.b as i128;
+    value.rem_euclid(self.modulus as i128) as i64
+  }
+
+  /// The function applying this one and then `next`.
+  pub fn then(&self, next: &Linear) -> Linear {
+    debug_assert_eq!(self.modulus, next.modulus);
+    Linear::new(
+      mul_mod(next.a, self.a, self.modulus),
+      next.apply(self.b),
+      self.modulus,
+    )
+  }
+
+  /// The function applying this one `times` times, by repeated squaring.
+  pub fn repeat(&self, mut times: u64) -> Linear {
+    let mut result = Linear::identity(self.modulus);
+    let mut step = *self;
+    while times > 0 {
+      if times & 1 == 1 {
+        result = result.then(&step);
+      }
+      step = step.then(&step);
+      times >>= 1;
+    }
+    result
+  }
+
+  /// The function undoing this one, which only exists if `a` and the modulus are coprime.
+  pub fn inverse(&self) -> Option<Linear> {
+    let a = inverse_mod(self.a, self.modulus)?;
+    Some(Linear::new(
+      a,
+      -mul_mod(a, self.b, self.modulus),
+      self.modulus,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn finds_divisors_and_multiples() {
+    assert_eq!(gcd(12, -18), 6);
+    assert_eq!(gcd(0, -5), 5);
+    assert_eq!(gcd(0, 0), 0);
+    assert_eq!(lcm(4, -6), 12);
+    assert_eq!(lcm(0, 6), 0);
+    assert_eq!(lcm_all([18, 28, 44]), 2772);
+    assert_eq!(lcm_all([]), 1);
+
+    for (a, b) in [(240, 46), (-7, 3), (0, 9), (5, 0)] {
+      let (g, x, y) = extended_gcd(a, b);
+      assert_eq!(g, gcd(a, b));
+      assert_eq!(a * x + b * y, g);
+    }
+  }
+
+  #[test]
+  fn does_modular_arithmetic() {
+    // Products of these overflow an `i64`
+    let big = 119_315_717_514_047;
+    assert_eq!(mul_mod(big - 1, big - 1, big), 1);
+    assert_eq!(pow_mod(2, 10, 1000), 24);
+    assert_eq!(pow_mod(-3, 3, 7), 1);
+    assert_eq!(pow_mod(5, 0, 1), 0);
+    assert_eq!(inverse_mod(3, 11), Some(4));
+    assert_eq!(inverse_mod(6, 9), None);
+    let inverse = inverse_mod(2020, big).unwrap();
+    assert_eq!(mul_mod(inverse, 2020, big), 1);
+  }
+
+  #[test]
+  fn composes_linear_functions() {
+    let double = Linear::new(2, 0, 10);
+    let increment = Linear::new(1, 1, 10);
+    assert_eq!(double.then(&increment).apply(4), 9);
+    assert_eq!(increment.then(&double).apply(4), 0);
+
+    let shuffle = Linear::new(7, -3, 10007);
+    let repeated = shuffle.repeat(1000);
+    let mut x = 2019;
+    for _ in 0..1000 {
+      x = shuffle.apply(x);
+    }
+    assert_eq!(repeated.apply(2019), x);
+    assert_eq!(repeated.inverse().unwrap().apply(x), 2019);
+    assert_eq!(
+      shuffle.then(&shuffle.inverse().unwrap()),
+      Linear::identity(10007)
+    );
+    assert_eq!(Linear::new(5, 1, 10).inverse(), None);
+  }
+}
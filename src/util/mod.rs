@@ -0,0 +1,132 @@
+pub mod cycle;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod parse;
+
+/// The digits of `num` in base `radix`, the most significant first and `.rev()` for the least
+/// significant first. `0` has the single digit `0`. Panics if `radix` is less than 2.
+pub fn digits(num: usize, radix: usize) -> Digits {
+  let count = digit_count(num, radix);
+  Digits {
+    num,
+    radix,
+    high: radix.pow(count - 1),
+    low: 1,
+    remaining: count,
+  }
+}
+
+/// The number of digits of `num` in base `radix`, `1` for `0`. Panics if `radix` is less than 2.
+pub fn digit_count(num: usize, radix: usize) -> u32 {
+  assert!(radix >= 2, "Radix {} is less than 2", radix);
+  num.checked_ilog(radix).unwrap_or(0) + 1
+}
+
+/// The digit of `num` in base `radix` worth `radix` to the power of `place`, `0` past the most
+/// significant digit. Panics if `radix` is less than 2.
+pub fn digit(num: usize, place: u32, radix: usize) -> usize {
+  assert!(radix >= 2, "Radix {} is less than 2", radix);
+  radix
+    .checked_pow(place)
+    .map_or(0, |value| num / value % radix)
+}
+
+/// The number with `digits` in base `radix`, the most significant first. `None` if a digit isn't
+/// below `radix` or the number doesn't fit. Panics if `radix` is less than 2.
+pub fn from_digits(digits: impl IntoIterator<Item = usize>, radix: usize) -> Option<usize> {
+  assert!(radix >= 2, "Radix {} is less than 2", radix);
+  digits.into_iter().try_fold(0_usize, |num, digit| {
+    if digit >= radix {
+      return None;
+    }
+    num.checked_mul(radix)?.checked_add(digit)
+  })
+}
+
+#[derive(Clone, Debug)]
+pub struct Digits {
+  num: usize,
+  radix: usize,
+  /// The place values of the most and least significant digits not yet taken.
+  high: usize,
+  low: usize,
+  remaining: u32,
+}
+
+impl Iterator for Digits {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    if self.remaining == 0 {
+      return None;
+    }
+    let digit = self.num / self.high % self.radix;
+    self.remaining -= 1;
+    self.high /= self.radix;
+    Some(digit)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining as usize, Some(self.remaining as usize))
+  }
+}
+
+impl DoubleEndedIterator for Digits {
+  fn next_back(&mut self) -> Option<usize> {
+    if self.remaining == 0 {
+      return None;
+    }
+    let digit = self.num / self.low % self.radix;
+    self.remaining -= 1;
+    // The place value past the most significant digit could overflow
+    if self.remaining > 0 {
+      self.low *= self.radix;
+    }
+    Some(digit)
+  }
+}
+
+impl ExactSizeIterator for Digits {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  #[should_panic(expected = "Radix 1 is less than 2")]
+  fn rejects_radix_below_2() {
+    digits(5, 1);
+  }
+
+  #[test]
+  fn splits_and_joins_digits() {
+    assert_eq!(digits(1234, 10).collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(digits(1234, 10).rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    assert_eq!(digits(0, 10).collect::<Vec<_>>(), [0]);
+    assert_eq!(digits(0b1011, 2).collect::<Vec<_>>(), [1, 0, 1, 1]);
+    assert_eq!(digits(usize::MAX, 16).len(), usize::BITS as usize / 4);
+
+    // Taking from both ends meets in the middle
+    let mut both = digits(90210, 10);
+    assert_eq!((both.next(), both.next_back()), (Some(9), Some(0)));
+    assert_eq!(both.collect::<Vec<_>>(), [0, 2, 1]);
+
+    assert_eq!(digit_count(0, 10), 1);
+    assert_eq!(digit_count(99, 10), 2);
+    assert_eq!(digit_count(255, 16), 2);
+    assert_eq!(digit(1002, 0, 10), 2);
+    assert_eq!(digit(1002, 3, 10), 1);
+    assert_eq!(digit(1002, 4, 10), 0);
+    assert_eq!(digit(1002, 40, 10), 0);
+
+    assert_eq!(from_digits(digits(4056, 10), 10), Some(4056));
+    assert_eq!(from_digits(digits(4056, 10).rev(), 10), Some(6504));
+    assert_eq!(from_digits([0xf, 0xf], 16), Some(255));
+    assert_eq!(from_digits([], 10), Some(0));
+    assert_eq!(from_digits([1, 10], 10), None);
+    assert_eq!(from_digits(digits(usize::MAX, 10), 10), Some(usize::MAX));
+    assert_eq!(from_digits(digits(usize::MAX, 10).chain([0]), 10), None);
+  }
+}
@@ -0,0 +1,254 @@
+//! Parsers for the shapes puzzle inputs come in, which report the line and column of malformed
+//! input.
+
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// A piece of the input and where it starts, 1-based.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span<'a> {
+  pub text: &'a str,
+  pub line: usize,
+  pub column: usize,
+}
+
+impl<'a> Span<'a> {
+  /// The whole input, starting at line 1, column 1.
+  pub fn new(text: &'a str) -> Span<'a> {
+    Span {
+      text,
+      line: 1,
+      column: 1,
+    }
+  }
+
+  /// Wraps `error` with where this span starts.
+  pub fn error(&self, error: impl Into<Error>) -> Error {
+    Error::at(self.line, self.column, error)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.text.is_empty()
+  }
+
+  /// Spans for `pieces`, which must be slices of this span's text in order.
+  fn spans(self, pieces: impl Iterator<Item = &'a str>) -> impl Iterator<Item = Span<'a>> {
+    let (mut offset, mut line, mut column) = (0, self.line, self.column);
+    pieces.map(move |piece| {
+      let start = piece.as_ptr() as usize - self.text.as_ptr() as usize;
+      for c in self.text[offset..start].chars() {
+        if c == '\n' {
+          line += 1;
+          column = 1;
+        } else {
+          column += 1;
+        }
+      }
+      offset = start;
+      Span {
+        text: piece,
+        line,
+        column,
+      }
+    })
+  }
+
+  fn slice(self, piece: &'a str) -> Span<'a> {
+    self.spans([piece].into_iter()).next().expect("One piece")
+  }
+
+  pub fn trim(self) -> Span<'a> {
+    self.slice(self.text.trim())
+  }
+
+  pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+    self.spans(self.text.lines())
+  }
+
+  /// Groups of lines separated by blank lines, each spanning from its first line to its last.
+  pub fn blocks(self) -> Vec<Span<'a>> {
+    let mut blocks = vec![];
+    let mut block: Option<(Span<'a>, Span<'a>)> = None;
+    for line in self.lines().chain([Span::new("")]) {
+      match block {
+        _ if !line.text.trim().is_empty() => {
+          block = Some((block.map_or(line, |(first, _)| first), line));
+        }
+        Some((first, last)) => {
+          let start = first.text.as_ptr() as usize - self.text.as_ptr() as usize;
+          let end = last.text.as_ptr() as usize - self.text.as_ptr() as usize + last.text.len();
+          blocks.push(Span {
+            text: &self.text[start..end],
+            ..first
+          });
+          block = None;
+        }
+        None => {}
+      }
+    }
+    blocks
+  }
+
+  pub fn split(self, separator: &'a str) -> impl Iterator<Item = Span<'a>> {
+    self.spans(self.text.split(separator))
+  }
+
+  /// The parts before and after the only `separator`, such as the key and value of `key=value`.
+  pub fn split_once(self, separator: &str) -> Result<(Span<'a>, Span<'a>)> {
+    let mut parts = self.spans(self.text.splitn(3, separator));
+    let (Some(first), Some(second)) = (parts.next(), parts.next()) else {
+      return Err(self.error(format!("Expected `{}` in `{}`", separator, self.text)));
+    };
+    if let Some(third) = parts.next() {
+      let at = third.column - separator.chars().count();
+      let error = format!("Expected a single `{}` in `{}`", separator, self.text);
+      return Err(Error::at(third.line, at, error));
+    }
+    Ok((first, second))
+  }
+
+  pub fn parse<T>(self) -> Result<T>
+  where
+    T: FromStr,
+    T::Err: Into<Error>,
+  {
+    self.text.parse().map_err(|error| self.error(error))
+  }
+
+  /// Values separated by `separator`, such as comma separated integers.
+  pub fn separated<T>(self, separator: &'a str) -> Result<Vec<T>>
+  where
+    T: FromStr,
+    T::Err: Into<Error>,
+  {
+    self.split(separator).map(Span::parse).collect()
+  }
+
+  /// A token made of a letter and a value, such as `U7`.
+  pub fn tagged<T>(self) -> Result<(char, T)>
+  where
+    T: FromStr,
+    T::Err: Into<Error>,
+  {
+    let mut chars = self.text.chars();
+    match chars.next() {
+      Some(tag) if tag.is_alphabetic() => Ok((tag, self.slice(chars.as_str()).parse()?)),
+      Some(c) => Err(self.error(format!("Expected a letter, found `{}`", c))),
+      None => Err(self.error("Expected a letter, found nothing")),
+    }
+  }
+
+  /// The characters with the spans they are in.
+  pub fn chars(self) -> impl Iterator<Item = (Span<'a>, char)> {
+    let pieces = self
+      .text
+      .char_indices()
+      .map(move |(i, c)| &self.text[i..i + c.len_utf8()]);
+    self
+      .spans(pieces)
+      .map(|span| (span, span.text.chars().next().expect("One char")))
+  }
+
+  /// Rows of cells, one per character of each line, such as a `#.` map. `cell` returns `None`
+  /// for characters that aren't valid cells. Every row must have as many cells as the first.
+  pub fn grid<T>(self, cell: impl Fn(char) -> Option<T>) -> Result<Vec<Vec<T>>> {
+    let mut rows: Vec<Vec<T>> = vec![];
+    for line in self.lines() {
+      let row = line
+        .chars()
+        .map(|(span, c)| cell(c).ok_or_else(|| span.error(format!("Invalid cell: {}", c))))
+        .collect::<Result<Vec<T>>>()?;
+      if let Some(first) = rows.first() {
+        if row.len() != first.len() {
+          let error = format!("Expected {} cells, found {}", first.len(), row.len());
+          return Err(line.error(error));
+        }
+      }
+      rows.push(row);
+    }
+    Ok(rows)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn error<T>(result: Result<T>) -> String {
+    result.err().expect("Expected an error").to_string()
+  }
+
+  #[test]
+  fn tracks_positions() {
+    let input = Span::new("1,2\n  3,4\n");
+    let lines: Vec<Span> = input.lines().map(Span::trim).collect();
+    assert_eq!(lines[1].line, 2);
+    assert_eq!(lines[1].column, 3);
+    assert_eq!(lines[1].separated::<u8>(",").unwrap(), [3, 4]);
+    assert_eq!(
+      error(
+        Span::new("1,2\n3,x")
+          .lines()
+          .nth(1)
+          .unwrap()
+          .separated::<u8>(",")
+      ),
+      "line 2, column 3: invalid digit found in string"
+    );
+  }
+
+  #[test]
+  fn parses_tokens_and_pairs() {
+    let line = Span::new("U7,R16");
+    let tokens: Vec<(char, u32)> = line
+      .split(",")
+      .map(|token| token.tagged().unwrap())
+      .collect();
+    assert_eq!(tokens, [('U', 7), ('R', 16)]);
+    assert_eq!(
+      error(
+        Span::new("U7,7R")
+          .split(",")
+          .nth(1)
+          .unwrap()
+          .tagged::<u32>()
+      ),
+      "line 1, column 4: Expected a letter, found `7`"
+    );
+
+    let (key, value) = Span::new("COM)B").split_once(")").unwrap();
+    assert_eq!((key.text, value.text, value.column), ("COM", "B", 5));
+    assert_eq!(
+      error(Span::new("A)B)C").split_once(")")),
+      "line 1, column 4: Expected a single `)` in `A)B)C`"
+    );
+    assert!(Span::new("AB").split_once(")").is_err());
+  }
+
+  #[test]
+  fn parses_grids_and_blocks() {
+    let cell = |c| match c {
+      '#' => Some(true),
+      '.' => Some(false),
+      _ => None,
+    };
+    let rows = Span::new(".#\n#.\n").grid(cell).unwrap();
+    assert_eq!(rows, [[false, true], [true, false]]);
+    assert_eq!(
+      error(Span::new(".#\n#?").grid(cell)),
+      "line 2, column 2: Invalid cell: ?"
+    );
+    assert_eq!(
+      error(Span::new(".#\n#").grid(cell)),
+      "line 2, column 1: Expected 2 cells, found 1"
+    );
+
+    let blocks = Span::new("\na\nb\n\n\nc\n").blocks();
+    let blocks: Vec<(&str, usize)> = blocks
+      .iter()
+      .map(|block| (block.text, block.line))
+      .collect();
+    assert_eq!(blocks, [("a\nb", 2), ("c", 6)]);
+  }
+}
@@ -0,0 +1,166 @@
+use std::{collections::HashMap, fmt, fs, path::PathBuf};
+
+use crate::{
+  runner::solve,
+  solution::{DynSolution, Example, Part},
+  Result,
+};
+
+/// Confirmed answers, stored one per line as `day<TAB>part<TAB>answer`. Newlines and backslashes
+/// in answers are escaped as `\n` and `\\`, and lines starting with `#` are comments.
+pub struct Answers {
+  answers: HashMap<(u8, u8), String>,
+}
+
+fn unescape(answer: &str) -> Result<String> {
+  let mut unescaped = String::new();
+  let mut chars = answer.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      unescaped.push(c);
+      continue;
+    }
+    match chars.next() {
+      Some('n') => unescaped.push('\n'),
+      Some('\\') => unescaped.push('\\'),
+      other => return Err(format!("Invalid escape: \\{}", other.unwrap_or(' ')).into()),
+    }
+  }
+  Ok(unescaped)
+}
+
+pub fn escape(answer: &str) -> String {
+  answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+impl Answers {
+  pub fn parse(source: &str) -> Result<Answers> {
+    let mut answers = HashMap::new();
+    for (i, line) in source.lines().enumerate() {
+      if line.trim().is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let fields: Vec<&str> = line.splitn(3, '\t').collect();
+      let [day, part, answer] = fields[..] else {
+        return Err(format!("Line {}: expected day, part and answer", i + 1).into());
+      };
+      let key = (day.parse::<u8>()?, part.parse::<u8>()?);
+      if answers.insert(key, unescape(answer)?).is_some() {
+        return Err(
+          format!(
+            "Line {}: duplicate answer for day {} part {}",
+            i + 1,
+            key.0,
+            key.1
+          )
+          .into(),
+        );
+      }
+    }
+    Ok(Answers { answers })
+  }
+
+  pub fn from_file(path: &PathBuf) -> Result<Answers> {
+    Answers::parse(&fs::read_to_string(path)?)
+  }
+
+  pub fn get(&self, day: u8, part: Part) -> Option<&String> {
+    self.answers.get(&(day, part.number()))
+  }
+
+  pub fn check(&self, day: u8, part: Part, answer: &Result<String>) -> Outcome {
+    match (answer, self.get(day, part)) {
+      (Err(error), _) => Outcome::Error(error.to_string()),
+      (Ok(actual), None) => Outcome::Unknown(actual.clone()),
+      (Ok(actual), Some(expected)) if actual == expected => Outcome::Pass,
+      (Ok(actual), Some(expected)) => Outcome::Fail {
+        expected: expected.clone(),
+        actual: actual.clone(),
+      },
+    }
+  }
+}
+
+/// Runs one of a day's published examples through the solution.
+pub fn check_example(solution: &dyn DynSolution, example: &Example) -> Outcome {
+  let answer = solve(solution, example.input, &[example.part])
+    .and_then(|mut results| results.pop().expect("One part was run").answer);
+  match answer {
+    Err(error) => Outcome::Error(error.to_string()),
+    Ok(actual) if actual == example.answer => Outcome::Pass,
+    Ok(actual) => Outcome::Fail {
+      expected: example.answer.to_string(),
+      actual,
+    },
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+  Pass,
+  Fail {
+    expected: String,
+    actual: String,
+  },
+  /// No confirmed answer to compare with.
+  Unknown(String),
+  Error(String),
+}
+
+impl Outcome {
+  pub fn is_failure(&self) -> bool {
+    matches!(self, Outcome::Fail { .. } | Outcome::Error(_))
+  }
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Outcome::Pass => write!(f, "pass"),
+      Outcome::Fail { expected, actual } => write!(
+        f,
+        "FAIL (expected {}, got {})",
+        escape(expected),
+        escape(actual)
+      ),
+      Outcome::Unknown(actual) => write!(f, "unknown (got {})", escape(actual)),
+      Outcome::Error(error) => write!(f, "FAIL ({})", error),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_escaped_answers() {
+    let answers = Answers::parse("# day\tpart\tanswer\n1\t1\t42\n8\t2\t █\\n█ \\\\\n").unwrap();
+    assert_eq!(answers.get(1, Part::One), Some(&"42".to_string()));
+    assert_eq!(answers.get(8, Part::Two), Some(&" █\n█ \\".to_string()));
+    assert_eq!(answers.get(1, Part::Two), None);
+    assert_eq!(escape(" █\n█ \\"), " █\\n█ \\\\");
+
+    assert!(Answers::parse("1\t1").is_err());
+    assert!(Answers::parse("1\t1\t2\n1\t1\t3").is_err());
+  }
+
+  #[test]
+  fn checks_answers() {
+    let answers = Answers::parse("3\t1\t159").unwrap();
+    let check = |part, answer: &str| answers.check(3, part, &Ok(answer.to_string()));
+
+    assert_eq!(check(Part::One, "159"), Outcome::Pass);
+    assert_eq!(
+      check(Part::One, "160"),
+      Outcome::Fail {
+        expected: "159".to_string(),
+        actual: "160".to_string()
+      }
+    );
+    assert_eq!(check(Part::Two, "610"), Outcome::Unknown("610".to_string()));
+    assert!(answers
+      .check(3, Part::One, &Err("oops".into()))
+      .is_failure());
+  }
+}
@@ -0,0 +1,228 @@
+use std::{
+  collections::BTreeMap,
+  fs,
+  path::PathBuf,
+  time::{Duration, SystemTime},
+};
+
+use crate::{
+  inputs::{Input, Resolver},
+  runner::solve,
+  solution::{DynSolution, Part},
+  Error, Result,
+};
+
+pub const DEFAULT_INTERVAL_MS: u64 = 500;
+
+/// The inputs to watch for a day: the one given with `--input`, otherwise its input and any
+/// alternates such as `day_N.sample.txt` next to it. The day's source isn't watched, as the running
+/// binary can't pick up changes to it.
+pub fn watched_inputs(day: u8, input: Option<&Input>, inputs: &Resolver) -> Result<Vec<PathBuf>> {
+  match input {
+    Some(Input::File(path)) => return Ok(vec![path.clone()]),
+    Some(Input::Stdin) => return Err("Can't watch stdin, give an input file instead".into()),
+    None => {}
+  }
+
+  let main = inputs.path(day);
+  let prefix = format!("day_{}.", day);
+  let mut alternates: Vec<PathBuf> = fs::read_dir(inputs.directory())?
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .filter(|path| {
+      let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+      name.starts_with(&prefix) && name.ends_with(".txt") && *path != main
+    })
+    .collect();
+  alternates.sort();
+
+  let mut watched = vec![main];
+  watched.extend(alternates);
+  Ok(watched)
+}
+
+/// Modification times of the watched files, `None` for missing ones.
+pub fn snapshot(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+  paths
+    .iter()
+    .map(|path| {
+      let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+      (path.clone(), modified.ok())
+    })
+    .collect()
+}
+
+/// Answers, or errors, and how long they took by input and part.
+pub type Answers = BTreeMap<(PathBuf, u8), (String, Duration)>;
+
+pub fn run_inputs(solution: &dyn DynSolution, paths: &[PathBuf], parts: &[Part]) -> Answers {
+  let mut answers = Answers::new();
+  for path in paths {
+    let results = fs::read_to_string(path)
+      .map_err(Error::from)
+      .and_then(|input| solve(solution, &input, parts));
+    match results {
+      Ok(results) => {
+        for result in results {
+          let answer = result
+            .answer
+            .unwrap_or_else(|error| format!("error: {}", error));
+          answers.insert(
+            (path.clone(), result.part.number()),
+            (answer, result.duration),
+          );
+        }
+      }
+      Err(error) => {
+        for part in parts {
+          let answer = format!("input error: {}", error);
+          answers.insert((path.clone(), part.number()), (answer, Duration::ZERO));
+        }
+      }
+    }
+  }
+  answers
+}
+
+/// A line per answer, noting how it and its duration changed since the previous run.
+pub fn describe(current: &Answers, previous: &Answers) -> Vec<String> {
+  current
+    .iter()
+    .map(|((path, part), (answer, duration))| {
+      let name = path.file_name().map_or(path.display().to_string(), |name| {
+        name.to_string_lossy().into_owned()
+      });
+      let mut line = format!("{} part {}: ", name, part);
+      // Multi-line answers such as images start on their own line
+      if answer.contains('\n') {
+        line.push('\n');
+      }
+      line.push_str(answer);
+      line.push_str(&format!(" ({:.2?}", duration));
+
+      if let Some((previous_answer, previous_duration)) = previous.get(&(path.clone(), *part)) {
+        let change = duration.as_secs_f64() - previous_duration.as_secs_f64();
+        let sign = if change < 0.0 { "-" } else { "+" };
+        line.push_str(&format!(
+          ", {}{:.2?}",
+          sign,
+          Duration::from_secs_f64(change.abs())
+        ));
+        if previous_answer != answer {
+          line.push_str(", changed");
+        }
+      }
+      line.push(')');
+      line
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{env, process};
+
+  use super::*;
+  use crate::solution::Solution;
+
+  struct Halves;
+
+  impl Solution for Halves {
+    type Input = u32;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(&self, input: &str) -> Result<u32> {
+      let number: u32 = input.trim().parse()?;
+      assert!(number.is_multiple_of(2), "{} is odd", number);
+      Ok(number)
+    }
+
+    fn part_one(&self, input: &u32) -> Result<u32> {
+      Ok(input / 2)
+    }
+
+    fn part_two(&self, input: &u32) -> Result<u32> {
+      Ok(input / 4)
+    }
+  }
+
+  #[test]
+  fn reports_panics_as_answers() {
+    let root = env::temp_dir().join(format!("watch_panics_{}", process::id()));
+    fs::create_dir_all(&root).unwrap();
+    let (even, odd) = (root.join("day_1.txt"), root.join("day_1.odd.txt"));
+    fs::write(&even, "8").unwrap();
+    fs::write(&odd, "7").unwrap();
+
+    let answers = run_inputs(&Halves, &[even.clone(), odd.clone()], &[Part::One]);
+    assert_eq!(answers[&(even, 1)].0, "4");
+    let panicked = &answers[&(odd, 1)].0;
+    assert!(panicked.ends_with("7 is odd"), "{}", panicked);
+
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn finds_inputs_to_watch() {
+    let root = env::temp_dir().join(format!("watch_{}", process::id()));
+    let year = root.join("2019");
+    fs::create_dir_all(&year).unwrap();
+    for name in [
+      "day_1.txt",
+      "day_1.sample.txt",
+      "day_10.txt",
+      "day_1.a.txt",
+      "notes.md",
+    ] {
+      fs::write(year.join(name), "").unwrap();
+    }
+    let inputs = Resolver {
+      root: root.clone(),
+      user: None,
+      year: 2019,
+      name: None,
+    };
+
+    let watched = watched_inputs(1, None, &inputs).unwrap();
+    let names: Vec<&str> = watched
+      .iter()
+      .map(|path| path.file_name().unwrap().to_str().unwrap())
+      .collect();
+    assert_eq!(names, ["day_1.txt", "day_1.a.txt", "day_1.sample.txt"]);
+    assert!(snapshot(&watched)
+      .iter()
+      .all(|(_, modified)| modified.is_some()));
+    assert_eq!(snapshot(&[year.join("day_2.txt")])[0].1, None);
+    assert!(watched_inputs(1, Some(&Input::Stdin), &inputs).is_err());
+
+    fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn describes_changes() {
+    let key = |part| (PathBuf::from("in/day_3.txt"), part);
+    let previous = Answers::from([
+      (key(1), ("159".to_string(), Duration::from_millis(3))),
+      (key(2), ("610".to_string(), Duration::from_millis(2))),
+    ]);
+    let current = Answers::from([
+      (key(1), ("159".to_string(), Duration::from_millis(1))),
+      (key(2), ("611".to_string(), Duration::from_millis(4))),
+    ]);
+
+    assert_eq!(
+      describe(&current, &previous),
+      [
+        "day_3.txt part 1: 159 (1.00ms, -2.00ms)",
+        "day_3.txt part 2: 611 (4.00ms, +2.00ms, changed)"
+      ]
+    );
+    assert_eq!(
+      describe(&current, &Answers::new())[0],
+      "day_3.txt part 1: 159 (1.00ms)"
+    );
+  }
+}
@@ -0,0 +1,26 @@
+use std::ops::RangeInclusive;
+
+use crate::solution::DynSolution;
+
+pub mod year_2019;
+
+/// Years with solved days, oldest first.
+pub const YEARS: &[u16] = &[2019];
+/// The year used when none is selected.
+pub const LATEST: u16 = 2019;
+
+/// The solved days of a year, `None` if the year has none.
+pub fn days(year: u16) -> Option<RangeInclusive<u8>> {
+  match year {
+    2019 => Some(year_2019::DAYS),
+    _ => None,
+  }
+}
+
+/// The solution for a day, `None` if the day isn't solved.
+pub fn solution(year: u16, day: u8) -> Option<&'static dyn DynSolution> {
+  match year {
+    2019 => year_2019::solution(day),
+    _ => None,
+  }
+}
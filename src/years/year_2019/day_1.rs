@@ -0,0 +1,89 @@
+use advent_of_code::util::parse::Span;
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Result,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+  type Input = Vec<u32>;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      part: Part::One,
+      input: "12",
+      answer: "2",
+    },
+    Example {
+      part: Part::One,
+      input: "14",
+      answer: "2",
+    },
+    Example {
+      part: Part::One,
+      input: "1969",
+      answer: "654",
+    },
+    Example {
+      part: Part::One,
+      input: "100756",
+      answer: "33583",
+    },
+    Example {
+      part: Part::Two,
+      input: "14",
+      answer: "2",
+    },
+    Example {
+      part: Part::Two,
+      input: "1969",
+      answer: "966",
+    },
+    Example {
+      part: Part::Two,
+      input: "100756",
+      answer: "50346",
+    },
+  ];
+
+  fn parse(&self, input: &str) -> Result<Vec<u32>> {
+    parse_module_masses(input)
+  }
+
+  fn part_one(&self, masses: &Vec<u32>) -> Result<u32> {
+    Ok(masses.iter().map(|mass| fuel_for_mass(*mass)).sum())
+  }
+
+  fn part_two(&self, masses: &Vec<u32>) -> Result<u32> {
+    Ok(total_fuel_requirement(masses))
+  }
+}
+
+fn parse_module_masses(input: &str) -> Result<Vec<u32>> {
+  Span::new(input).lines().map(Span::parse).collect()
+}
+
+/// Fuel for the mass alone, without the fuel's own mass.
+fn fuel_for_mass(mass: u32) -> u32 {
+  (mass / 3).saturating_sub(2)
+}
+
+fn module_fuel_requirement(mass: u32) -> u32 {
+  if mass / 3 <= 2 {
+    return 0;
+  }
+
+  let fuel = (mass / 3) - 2;
+  fuel + module_fuel_requirement(fuel)
+}
+
+pub fn total_fuel_requirement(masses: &[u32]) -> u32 {
+  masses
+    .iter()
+    .map(|mass| module_fuel_requirement(*mass))
+    .sum::<u32>()
+}
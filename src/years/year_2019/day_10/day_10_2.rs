@@ -0,0 +1,38 @@
+use advent_of_code::util::geometry::Point;
+
+use super::{best_location, find_reachable, AsteroidMap};
+use crate::Result;
+
+/// Vaporizes asteroids with a laser rotating clockwise from up, starting at the best location, and
+/// returns `x * 100 + y` of the 200th one.
+pub fn two_hundredth_vaporized(asteroids: &AsteroidMap) -> Result<isize> {
+  let asteroids = &mut asteroids.clone();
+  let (_, station) = best_location(asteroids).ok_or("No asteroids found")?;
+  let mut by_angle: Vec<Point> = reachable_by_angle(&station, asteroids);
+
+  let mut last_destroyed = Point::ORIGIN;
+  for _ in 1..=200 {
+    if by_angle.is_empty() {
+      by_angle = reachable_by_angle(&station, asteroids);
+    }
+    if by_angle.is_empty() {
+      return Err("Fewer than 200 asteroids to vaporize".into());
+    }
+
+    last_destroyed = by_angle.remove(0);
+    asteroids.remove(&last_destroyed);
+  }
+
+  Ok(last_destroyed.x * 100 + last_destroyed.y)
+}
+
+fn reachable_by_angle(station: &Point, asteroids: &AsteroidMap) -> Vec<Point> {
+  let reachable = find_reachable(station, asteroids);
+  sort_asteroids_by_angle(station, &reachable)
+}
+
+fn sort_asteroids_by_angle(station: &Point, asteroids: &AsteroidMap) -> Vec<Point> {
+  let mut sorted: Vec<Point> = asteroids.iter().copied().collect();
+  sorted.sort_by(|a, b| (*a - *station).clockwise_cmp(&(*b - *station)));
+  sorted
+}
@@ -0,0 +1,187 @@
+use advent_of_code::util::{geometry::Point, grid::Grid};
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Result,
+};
+use std::collections::HashSet;
+
+use self::day_10_2::two_hundredth_vaporized;
+
+mod day_10_2;
+
+type AsteroidMap = HashSet<Point>;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  type Input = AsteroidMap;
+  type PartOne = usize;
+  type PartTwo = isize;
+
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      part: Part::One,
+      input: ".#..#\n\
+        .....\n\
+        #####\n\
+        ....#\n\
+        ...##",
+      answer: "8",
+    },
+    Example {
+      part: Part::One,
+      input: "......#.#.\n\
+        #..#.#....\n\
+        ..#######.\n\
+        .#.#.###..\n\
+        .#..#.....\n\
+        ..#....#.#\n\
+        #..#....#.\n\
+        .##.#..###\n\
+        ##...#..#.\n\
+        .#....####",
+      answer: "33",
+    },
+    Example {
+      part: Part::One,
+      input: "#.#...#.#.\n\
+        .###....#.\n\
+        .#....#...\n\
+        ##.#.#.#.#\n\
+        ....#.#.#.\n\
+        .##..###.#\n\
+        ..#...##..\n\
+        ..##....##\n\
+        ......#...\n\
+        .####.###.",
+      answer: "35",
+    },
+    Example {
+      part: Part::One,
+      input: ".#..#..###\n\
+        ####.###.#\n\
+        ....###.#.\n\
+        ..###.##.#\n\
+        ##.##.#.#.\n\
+        ....###..#\n\
+        ..#.#..#.#\n\
+        #..#.#.###\n\
+        .##...##.#\n\
+        .....#.#..",
+      answer: "41",
+    },
+    Example {
+      part: Part::One,
+      input: ".#..##.###...#######\n\
+        ##.############..##.\n\
+        .#.######.########.#\n\
+        .###.#######.####.#.\n\
+        #####.##.#.##.###.##\n\
+        ..#####..#.#########\n\
+        ####################\n\
+        #.####....###.#.#.##\n\
+        ##.#################\n\
+        #####.##.###..####..\n\
+        ..######..##.#######\n\
+        ####.##.####...##..#\n\
+        .#####..#.######.###\n\
+        ##...#.##########...\n\
+        #.##########.#######\n\
+        .####.#.###.###.#.##\n\
+        ....##.##.###..#####\n\
+        .#.#.###########.###\n\
+        #.#.#.#####.####.###\n\
+        ###.##.####.##.#..##",
+      answer: "210",
+    },
+    Example {
+      part: Part::Two,
+      input: ".#..##.###...#######\n\
+        ##.############..##.\n\
+        .#.######.########.#\n\
+        .###.#######.####.#.\n\
+        #####.##.#.##.###.##\n\
+        ..#####..#.#########\n\
+        ####################\n\
+        #.####....###.#.#.##\n\
+        ##.#################\n\
+        #####.##.###..####..\n\
+        ..######..##.#######\n\
+        ####.##.####...##..#\n\
+        .#####..#.######.###\n\
+        ##...#.##########...\n\
+        #.##########.#######\n\
+        .####.#.###.###.#.##\n\
+        ....##.##.###..#####\n\
+        .#.#.###########.###\n\
+        #.#.#.#####.####.###\n\
+        ###.##.####.##.#..##",
+      answer: "802",
+    },
+  ];
+
+  fn parse(&self, input: &str) -> Result<AsteroidMap> {
+    parse_asteroid_map(input)
+  }
+
+  /// Most asteroids detected from the best location
+  fn part_one(&self, asteroids: &AsteroidMap) -> Result<usize> {
+    let (max_reachable, _) = best_location(asteroids).ok_or("No asteroids found")?;
+    Ok(max_reachable)
+  }
+
+  fn part_two(&self, asteroids: &AsteroidMap) -> Result<isize> {
+    two_hundredth_vaporized(asteroids)
+  }
+}
+
+fn parse_asteroid_map(input: &str) -> Result<AsteroidMap> {
+  let map = Grid::parse(input, |c| match c {
+    '#' => Some(true),
+    '.' => Some(false),
+    _ => None,
+  })?;
+
+  Ok(
+    map
+      .iter()
+      .filter(|(_, &asteroid)| asteroid)
+      .map(|(point, _)| point)
+      .collect(),
+  )
+}
+
+fn find_reachable(from_pos: &Point, asteroids: &AsteroidMap) -> AsteroidMap {
+  let mut min_vecs: HashSet<Point> = HashSet::new();
+  let mut reachable: AsteroidMap = HashSet::new();
+
+  for target in asteroids {
+    if target == from_pos {
+      continue;
+    }
+
+    let min_vec = (*target - *from_pos).reduced();
+    if !min_vecs.contains(&min_vec) {
+      min_vecs.insert(min_vec);
+      reachable.insert(*target);
+    }
+  }
+
+  reachable
+}
+
+fn best_location(asteroids: &AsteroidMap) -> Option<(usize, Point)> {
+  let mut max_reachable = 0;
+  let mut best_location: Option<Point> = None;
+
+  for asteroid in asteroids {
+    let reachable = find_reachable(asteroid, asteroids);
+    if reachable.len() > max_reachable {
+      max_reachable = reachable.len();
+      best_location = Some(*asteroid);
+    }
+  }
+
+  best_location.map(|pos| (max_reachable, pos))
+}
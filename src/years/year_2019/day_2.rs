@@ -0,0 +1,63 @@
+use advent_of_code::intcode::{
+  limits::{RunLimits, RunResult},
+  program::Program,
+};
+
+use crate::{solution::Solution, Result};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+  type Input = Program;
+  type PartOne = isize;
+  type PartTwo = usize;
+
+  // The published examples only show memory after running programs that can't be given the 1202
+  // patch, so there are none to check the parts with.
+
+  fn parse(&self, input: &str) -> Result<Program> {
+    input.parse()
+  }
+
+  fn part_one(&self, program: &Program) -> Result<isize> {
+    let mut program = program.clone();
+    restore_intcode_program(&mut program);
+    program.run(None)?;
+    Ok(program.memory[0])
+  }
+
+  fn part_two(&self, program: &Program) -> Result<usize> {
+    let (noun, verb) = find_noun_verb(program)?;
+    Ok(100 * noun + verb)
+  }
+}
+
+pub fn restore_intcode_program(program: &mut Program) {
+  program.memory[1] = 12;
+  program.memory[2] = 2;
+}
+
+pub const TARGET_OUTPUT: usize = 19690720;
+/// Some patches send the program into an endless loop, so each attempt is cut off after this many
+/// instructions.
+const MAX_STEPS: usize = 10_000;
+
+pub fn find_noun_verb(original_program: &Program) -> Result<(usize, usize)> {
+  let limits = RunLimits::default().with_max_steps(MAX_STEPS);
+  for noun in 0..=99 {
+    for verb in 0..=99 {
+      let mut program = original_program.clone();
+      program.memory[1] = noun;
+      program.memory[2] = verb;
+      let halted = matches!(
+        program.run_with_limits(None, &limits)?,
+        RunResult::Halted(_)
+      );
+      if halted && program.memory[0] == TARGET_OUTPUT as isize {
+        return Ok((noun as usize, verb as usize));
+      }
+    }
+  }
+
+  Err("No noun and verb found that produce the target output".into())
+}
@@ -0,0 +1,153 @@
+use advent_of_code::util::{
+  geometry::{Direction, Point},
+  parse::Span,
+};
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Context, Result,
+};
+use std::collections::{HashMap, HashSet};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+  type Input = (Vec<Segment>, Vec<Segment>);
+  type PartOne = i32;
+  type PartTwo = usize;
+
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      part: Part::One,
+      input: "R8,U5,L5,D3\nU7,R6,D4,L4",
+      answer: "6",
+    },
+    Example {
+      part: Part::One,
+      input: "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+      answer: "159",
+    },
+    Example {
+      part: Part::One,
+      input: "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+      answer: "135",
+    },
+    Example {
+      part: Part::Two,
+      input: "R8,U5,L5,D3\nU7,R6,D4,L4",
+      answer: "30",
+    },
+    Example {
+      part: Part::Two,
+      input: "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+      answer: "610",
+    },
+    Example {
+      part: Part::Two,
+      input: "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+      answer: "410",
+    },
+  ];
+
+  fn parse(&self, input: &str) -> Result<Self::Input> {
+    read_wire_paths(input)
+  }
+
+  fn part_one(&self, (wire_1, wire_2): &Self::Input) -> Result<i32> {
+    let intersection = closest_wire_intersection(wire_1, wire_2)?;
+    Ok(intersection.manhattan(Point::ORIGIN))
+  }
+
+  fn part_two(&self, (wire_1, wire_2): &Self::Input) -> Result<usize> {
+    lowest_steps_to_intersection(wire_1, wire_2)
+  }
+}
+
+/// A straight part of a wire and its length.
+type Segment = (Direction, u32);
+
+fn parse_wire_path(wire: Span) -> Result<Vec<Segment>> {
+  wire
+    .split(",")
+    .map(|segment| {
+      let (direction, distance) = segment.tagged()?;
+      let direction = Direction::try_from(direction).map_err(|error| segment.error(error))?;
+      Ok((direction, distance))
+    })
+    .collect::<Result<Vec<_>>>()
+}
+
+fn read_wire_paths(input: &str) -> Result<(Vec<Segment>, Vec<Segment>)> {
+  let mut lines = Span::new(input).lines();
+  let wire_1 = lines.next();
+  let wire_2 = lines.next();
+
+  if let (Some(wire_1), Some(wire_2)) = (wire_1, wire_2) {
+    Ok((parse_wire_path(wire_1)?, parse_wire_path(wire_2)?))
+  } else {
+    Err("Invalid input, must be at least two lines".into())
+  }
+}
+
+/// Every point the wire goes through from the central port at the origin, which isn't included.
+fn wire_points(wire: &[Segment]) -> Vec<Point<i32>> {
+  let mut cords = vec![];
+  let mut point = Point::ORIGIN;
+
+  for (direction, distance) in wire {
+    for _ in 0..*distance {
+      point += direction.offset();
+      cords.push(point);
+    }
+  }
+
+  cords
+}
+
+fn points_intersections(
+  wire_1_points: &[Point<i32>],
+  wire_2_points: &[Point<i32>],
+) -> HashSet<Point<i32>> {
+  let wire_1_set: HashSet<Point<i32>> = HashSet::from_iter(wire_1_points.iter().copied());
+  let wire_2_set: HashSet<Point<i32>> = HashSet::from_iter(wire_2_points.iter().copied());
+  wire_1_set.intersection(&wire_2_set).copied().collect()
+}
+
+pub fn closest_wire_intersection(wire_1: &[Segment], wire_2: &[Segment]) -> Result<Point<i32>> {
+  let wire_1_points = wire_points(wire_1);
+  let wire_2_points = wire_points(wire_2);
+  let intersections = points_intersections(&wire_1_points, &wire_2_points);
+
+  intersections
+    .into_iter()
+    .min_by_key(|intersection| intersection.manhattan(Point::ORIGIN))
+    .context("No intersections found")
+}
+
+/// Steps the wire takes to first reach each of its points.
+fn steps_to_points(points: &[Point<i32>]) -> HashMap<Point<i32>, usize> {
+  let mut steps = HashMap::new();
+  for (i, point) in points.iter().enumerate() {
+    // Plus one as steps start at 1 while index starts at 0
+    steps.entry(*point).or_insert(i + 1);
+  }
+  steps
+}
+
+fn total_steps_to_intersections(wire_1: &[Segment], wire_2: &[Segment]) -> Vec<usize> {
+  let wire_1_steps = steps_to_points(&wire_points(wire_1));
+  let wire_2_steps = steps_to_points(&wire_points(wire_2));
+
+  wire_1_steps
+    .iter()
+    .filter_map(|(point, steps_1)| Some(steps_1 + wire_2_steps.get(point)?))
+    .collect()
+}
+
+pub fn lowest_steps_to_intersection(wire_1: &[Segment], wire_2: &[Segment]) -> Result<usize> {
+  let steps_to_intersections = total_steps_to_intersections(wire_1, wire_2);
+  steps_to_intersections
+    .into_iter()
+    .min()
+    .context("No intersections found")
+}
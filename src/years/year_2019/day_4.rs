@@ -0,0 +1,94 @@
+use advent_of_code::util::{digits, parse::Span};
+use itertools::Itertools;
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Result,
+};
+use std::ops::RangeInclusive;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+  type Input = RangeInclusive<usize>;
+  type PartOne = usize;
+  type PartTwo = usize;
+
+  /// The examples are single passwords, given as ranges holding just that password.
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      part: Part::One,
+      input: "111111-111111",
+      answer: "1",
+    },
+    Example {
+      part: Part::One,
+      input: "223450-223450",
+      answer: "0",
+    },
+    Example {
+      part: Part::One,
+      input: "123789-123789",
+      answer: "0",
+    },
+    Example {
+      part: Part::Two,
+      input: "112233-112233",
+      answer: "1",
+    },
+    Example {
+      part: Part::Two,
+      input: "123444-123444",
+      answer: "0",
+    },
+    Example {
+      part: Part::Two,
+      input: "111122-111122",
+      answer: "1",
+    },
+  ];
+
+  /// The puzzle input is the range as `lower-upper`.
+  fn parse(&self, input: &str) -> Result<RangeInclusive<usize>> {
+    let (lower, upper) = Span::new(input).trim().split_once("-")?;
+    Ok(lower.parse()?..=upper.parse()?)
+  }
+
+  fn part_one(&self, range: &RangeInclusive<usize>) -> Result<usize> {
+    Ok(possible_passwords_count(
+      range.clone(),
+      matching_adjacent_digits,
+    ))
+  }
+
+  fn part_two(&self, range: &RangeInclusive<usize>) -> Result<usize> {
+    Ok(possible_passwords_count(
+      range.clone(),
+      exactly_two_matching_adjacent_digits,
+    ))
+  }
+}
+
+fn matching_adjacent_digits(num: usize) -> bool {
+  digits(num, 10).tuple_windows().any(|(a, b)| a == b)
+}
+
+fn exactly_two_matching_adjacent_digits(num: usize) -> bool {
+  digits(num, 10)
+    .dedup_with_count()
+    .any(|(count, _)| count == 2)
+}
+
+fn digits_do_not_decrease(num: usize) -> bool {
+  digits(num, 10).tuple_windows().all(|(a, b)| a <= b)
+}
+
+/// Passwords in `range` whose digits never decrease and that meet the adjacent digits criterion.
+pub fn possible_passwords_count(
+  range: RangeInclusive<usize>,
+  adjacent_digits_criterion: fn(usize) -> bool,
+) -> usize {
+  range
+    .filter(|num| adjacent_digits_criterion(*num) && digits_do_not_decrease(*num))
+    .count()
+}
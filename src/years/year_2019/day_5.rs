@@ -0,0 +1,67 @@
+use advent_of_code::intcode::program::Program;
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Result,
+};
+
+pub struct Day5;
+
+const AIR_CONDITIONER_ID: isize = 1;
+const THERMAL_RADIATOR_ID: isize = 5;
+
+impl Solution for Day5 {
+  type Input = Program;
+  type PartOne = isize;
+  type PartTwo = isize;
+
+  const EXAMPLES: &'static [Example] = &[
+    // Outputs its input
+    Example {
+      part: Part::One,
+      input: "3,0,4,0,99",
+      answer: "1",
+    },
+    // Outputs whether the input is 8
+    Example {
+      part: Part::Two,
+      input: "3,9,8,9,10,9,4,9,99,-1,8",
+      answer: "0",
+    },
+    Example {
+      part: Part::Two,
+      input: "3,3,1107,-1,8,3,4,3,99",
+      answer: "1",
+    },
+    // Outputs 999, 1000 or 1001 for an input below, equal to or above 8
+    Example {
+      part: Part::Two,
+      input: "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,\
+        4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99",
+      answer: "999",
+    },
+  ];
+
+  fn parse(&self, input: &str) -> Result<Program> {
+    input.parse()
+  }
+
+  fn part_one(&self, program: &Program) -> Result<isize> {
+    diagnostic_code(program, AIR_CONDITIONER_ID)
+  }
+
+  fn part_two(&self, program: &Program) -> Result<isize> {
+    diagnostic_code(program, THERMAL_RADIATOR_ID)
+  }
+}
+
+/// Runs the TEST program for a system. Every output but the last is a test result, which is 0 when
+/// the test passed, and the last is the diagnostic code.
+fn diagnostic_code(program: &Program, system_id: isize) -> Result<isize> {
+  let output = program.clone().run(Some(vec![system_id]))?;
+  match output.split_last() {
+    Some((code, tests)) if tests.iter().all(|test| *test == 0) => Ok(*code),
+    Some(_) => Err(format!("TEST program reported failing tests: {:?}", output).into()),
+    None => Err("No output from TEST program".into()),
+  }
+}
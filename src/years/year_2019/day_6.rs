@@ -0,0 +1,115 @@
+use advent_of_code::util::{graph, parse::Span};
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Context, Result,
+};
+use std::collections::HashMap;
+
+type OrbitMap = HashMap<String, Option<String>>;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+  type Input = OrbitMap;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      part: Part::One,
+      input: "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L",
+      answer: "42",
+    },
+    Example {
+      part: Part::Two,
+      input: "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN",
+      answer: "4",
+    },
+  ];
+
+  fn parse(&self, input: &str) -> Result<OrbitMap> {
+    parse_orbit_map(input)
+  }
+
+  fn part_one(&self, map: &OrbitMap) -> Result<u32> {
+    orbit_count_checksum(map)
+  }
+
+  fn part_two(&self, map: &OrbitMap) -> Result<u32> {
+    min_orbital_transfers("YOU", "SAN", map)
+  }
+}
+
+fn parse_orbit_map(input: &str) -> Result<OrbitMap> {
+  let mut objects_map: OrbitMap = HashMap::new();
+  for line in Span::new(input).lines() {
+    let (center, object) = line.split_once(")")?;
+
+    objects_map
+      .entry(center.text.to_string())
+      .or_insert_with(|| None);
+
+    objects_map.insert(object.text.to_string(), Some(center.text.to_string()));
+  }
+
+  Ok(objects_map)
+}
+
+fn orbit_count_checksum(map: &OrbitMap) -> Result<u32> {
+  let mut checksum = 0;
+  for object in map.keys() {
+    checksum += path_to_com(object, map)?.len() as u32;
+  }
+  Ok(checksum)
+}
+
+/// Does not include the object itself
+fn path_to_com<'a>(object: &str, map: &'a OrbitMap) -> Result<Vec<&'a String>> {
+  let missing = |object: &str| format!("{} is missing from the orbit map", object);
+  let mut path: Vec<&String> = vec![];
+  let mut current: &Option<String> = map.get(object).with_context(|| missing(object))?;
+
+  while let Some(parent) = current {
+    // A path longer than the map's objects has to go around a loop
+    if path.len() == map.len() {
+      return Err(format!("{} is in an orbit loop", object).into());
+    }
+    path.push(parent);
+    current = map.get(parent).with_context(|| missing(parent))?;
+  }
+
+  Ok(path)
+}
+
+/// The fewest moves between neighbouring orbits taking `start` from the object it orbits to the
+/// one `target` orbits.
+fn min_orbital_transfers(start: &str, target: &str, map: &OrbitMap) -> Result<u32> {
+  let center = |object: &str| {
+    map
+      .get(object)
+      .with_context(|| format!("{} is missing from the orbit map", object))?
+      .as_deref()
+      .with_context(|| format!("{} doesn't orbit anything", object))
+  };
+  let (from, to) = (center(start)?, center(target)?);
+
+  // A transfer can go to the object being orbited or to one orbiting it
+  let mut neighbours: HashMap<&str, Vec<&str>> = HashMap::new();
+  for (object, center) in map {
+    if let Some(center) = center {
+      neighbours.entry(object).or_default().push(center);
+      neighbours.entry(center).or_default().push(object);
+    }
+  }
+
+  let search = graph::bfs(
+    [from],
+    |object| neighbours.get(object).cloned().unwrap_or_default(),
+    |&object| object == to,
+  );
+  let transfers = search
+    .goal_cost()
+    .with_context(|| format!("No orbital transfers lead from {} to {}", start, target))?;
+  Ok(transfers as u32)
+}
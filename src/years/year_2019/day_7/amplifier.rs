@@ -0,0 +1,49 @@
+use advent_of_code::intcode::{
+  instruction::{InstructionIo, InstructionResult},
+  opcode::Opcode,
+  program::Program,
+};
+
+use crate::Result;
+
+pub struct Amplifier {
+  program: Program,
+  phase: Option<u32>,
+}
+
+impl Amplifier {
+  pub fn new(program: Program, phase: u32) -> Self {
+    Self {
+      program,
+      phase: Some(phase),
+    }
+  }
+
+  /// The amplifier's next output signal, or `None` once it halts.
+  pub fn run(&mut self, signal: u32) -> Result<Option<u32>> {
+    loop {
+      let opcode = self.program.current_opcode()?;
+      if opcode == Opcode::Halt {
+        break;
+      }
+
+      let instruction = self.program.get_instruction(opcode)?;
+      let input = [self.phase.map_or(signal as isize, |p| p as isize)];
+      let mut io = InstructionIo::new(&input, &[]);
+      let result: InstructionResult = self.program.run_instruction(&instruction, &mut io)?;
+
+      if io.read() > 0 && self.phase.is_some() {
+        self.phase = None;
+      }
+
+      if let Some(output) = result.output {
+        let signal = output
+          .try_into()
+          .map_err(|_| format!("Amplifier output {} is not a valid signal", output))?;
+        return Ok(Some(signal));
+      }
+    }
+
+    Ok(None)
+  }
+}
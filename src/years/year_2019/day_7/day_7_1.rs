@@ -0,0 +1,32 @@
+use advent_of_code::intcode::program::Program;
+
+use super::amplifier::Amplifier;
+use crate::{Context, Result};
+use itertools::Itertools;
+
+fn run_amplifier(phase: u32, signal: u32, program: &mut Program) -> Result<u32> {
+  let mut amp = Amplifier::new(program.clone(), phase);
+  amp
+    .run(signal)?
+    .with_context(|| format!("Amplifier with phase {} halted without a signal", phase))
+}
+
+fn thruster_signal(phases: Vec<u32>, program: &mut Program) -> Result<u32> {
+  let mut signal = 0;
+  for phase in phases {
+    signal = run_amplifier(phase, signal, program)?;
+  }
+  Ok(signal)
+}
+
+pub fn max_thruster_signal(program: &Program) -> Result<u32> {
+  let mut highest = 0;
+  for phases in (0..=4).permutations(5) {
+    let signal = thruster_signal(phases, &mut (program.clone()))?;
+    if signal > highest {
+      highest = signal;
+    }
+  }
+
+  Ok(highest)
+}
@@ -0,0 +1,37 @@
+use super::amplifier::Amplifier;
+use crate::Result;
+use advent_of_code::intcode::program::Program;
+use itertools::Itertools;
+
+pub fn max_thruster_signal(program: &Program) -> Result<u32> {
+  let mut highest = 0;
+  for phases in (5..=9).permutations(5) {
+    let amplifiers: Vec<Amplifier> = phases
+      .into_iter()
+      .map(|phase| Amplifier::new(program.clone(), phase))
+      .collect();
+
+    let signal = run_feedback_loop(amplifiers)?;
+    if signal > highest {
+      highest = signal;
+    }
+  }
+
+  Ok(highest)
+}
+
+fn run_feedback_loop(mut amplifiers: Vec<Amplifier>) -> Result<u32> {
+  let mut signal = 0;
+
+  loop {
+    for amp in amplifiers.iter_mut() {
+      let result = amp.run(signal)?;
+
+      if let Some(result) = result {
+        signal = result;
+      } else {
+        return Ok(signal);
+      }
+    }
+  }
+}
@@ -0,0 +1,63 @@
+use advent_of_code::intcode::program::Program;
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Result,
+};
+
+mod amplifier;
+mod day_7_1;
+mod day_7_2;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+  type Input = Program;
+  type PartOne = u32;
+  type PartTwo = u32;
+
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      part: Part::One,
+      input: "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+      answer: "43210",
+    },
+    Example {
+      part: Part::One,
+      input: "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0",
+      answer: "54321",
+    },
+    Example {
+      part: Part::One,
+      input: "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,\
+        31,4,31,99,0,0,0",
+      answer: "65210",
+    },
+    Example {
+      part: Part::Two,
+      input: "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,\
+        5",
+      answer: "139629729",
+    },
+    Example {
+      part: Part::Two,
+      input:
+        "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,\
+        53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,\
+        10",
+      answer: "18216",
+    },
+  ];
+
+  fn parse(&self, input: &str) -> Result<Program> {
+    input.parse()
+  }
+
+  fn part_one(&self, program: &Program) -> Result<u32> {
+    day_7_1::max_thruster_signal(program)
+  }
+
+  fn part_two(&self, program: &Program) -> Result<u32> {
+    day_7_2::max_thruster_signal(program)
+  }
+}
@@ -0,0 +1,74 @@
+use advent_of_code::util::{grid::Grid, parse::Span};
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Result,
+};
+
+use self::space_image::{parse_pixels, Color, SpaceImage};
+
+mod space_image;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+  type Input = SpaceImage;
+  type PartOne = usize;
+  type PartTwo = String;
+
+  const EXAMPLES: &'static [Example] = &[
+    // The part one example uses digits that aren't colors, so only the decoding one is checked
+    Example {
+      part: Part::Two,
+      input: "2x2\n0222112222120000",
+      answer: " █\n█ ",
+    },
+  ];
+
+  /// The image data may be preceded by a `WIDTHxHEIGHT` line, otherwise it is 25x6.
+  fn parse(&self, input: &str) -> Result<SpaceImage> {
+    let mut lines = Span::new(input).lines();
+    match lines.next().filter(|line| line.text.contains('x')) {
+      Some(header) => {
+        let (width, height) = header.split_once("x")?;
+        let (width, height) = (width.trim().parse()?, height.trim().parse()?);
+        SpaceImage::from_digits(parse_pixels(lines)?, width, height)
+      }
+      None => SpaceImage::from_str(input, WIDTH, HEIGHT),
+    }
+  }
+
+  fn part_one(&self, image: &SpaceImage) -> Result<usize> {
+    let layer = find_layer_fewest_zeroes(image)?;
+    let count = |color: Color| layer.iter().filter(|(_, pixel)| **pixel == color).count();
+    Ok(count(Color::White) * count(Color::Transparent))
+  }
+
+  fn part_two(&self, image: &SpaceImage) -> Result<String> {
+    Ok(image.render())
+  }
+}
+
+fn find_layer_fewest_zeroes(image: &SpaceImage) -> Result<&Grid<Color>> {
+  let mut fewest_zeroes = image.width * image.height + 1;
+  let mut fewest_zeroes_layer: Option<&Grid<Color>> = None;
+
+  for layer in &image.layers {
+    let zeroes = layer
+      .iter()
+      .filter(|(_, pixel)| **pixel == Color::Black)
+      .count();
+    if zeroes < fewest_zeroes {
+      fewest_zeroes = zeroes;
+      fewest_zeroes_layer = Some(layer);
+    }
+  }
+
+  match fewest_zeroes_layer {
+    Some(layer) => Ok(layer),
+    None => Err("No layer found with less than maximum possible zeroes".into()),
+  }
+}
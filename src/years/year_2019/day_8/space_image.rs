@@ -0,0 +1,98 @@
+use advent_of_code::util::{grid::Grid, parse::Span};
+
+use crate::{Error, Result};
+
+#[derive(PartialEq, Clone)]
+pub enum Color {
+  Black,
+  White,
+  Transparent,
+}
+
+impl TryFrom<u8> for Color {
+  type Error = Error;
+
+  fn try_from(digit: u8) -> Result<Color> {
+    match digit {
+      0 => Ok(Color::Black),
+      1 => Ok(Color::White),
+      2 => Ok(Color::Transparent),
+      _ => Err(format!("Invalid color digit: {}", digit).into()),
+    }
+  }
+}
+
+impl Color {
+  pub fn symbol(&self) -> char {
+    match self {
+      Color::Black => ' ',
+      Color::White => '█',
+      Color::Transparent => '?',
+    }
+  }
+}
+
+/// The pixel digits of image data spread over `lines`, ignoring whitespace.
+pub fn parse_pixels<'a>(lines: impl Iterator<Item = Span<'a>>) -> Result<Vec<u8>> {
+  let mut digits = vec![];
+  for (span, c) in lines.flat_map(Span::chars) {
+    match c.to_digit(10) {
+      Some(digit @ 0..=2) => digits.push(digit as u8),
+      _ if c.is_whitespace() => {}
+      _ => return Err(span.error(format!("Invalid pixel: {}", c))),
+    }
+  }
+  Ok(digits)
+}
+
+pub struct SpaceImage {
+  pub layers: Vec<Grid<Color>>,
+  pub width: usize,
+  pub height: usize,
+}
+
+impl SpaceImage {
+  pub fn from_digits(digits: Vec<u8>, width: usize, height: usize) -> Result<SpaceImage> {
+    if width == 0 || height == 0 {
+      return Err(format!("Invalid image size: {}x{}", width, height).into());
+    }
+    let layer_chunks = digits.chunks_exact(width * height);
+    if !layer_chunks.remainder().is_empty() {
+      return Err("Image data does not match dimensions".into());
+    }
+
+    let layers: Vec<Grid<Color>> = layer_chunks
+      .map(|chunk| {
+        let pixels = chunk.iter().map(|&pixel| Color::try_from(pixel));
+        Grid::new(width, height, pixels.collect::<Result<_>>()?)
+      })
+      .collect::<Result<_>>()?;
+
+    Ok(SpaceImage {
+      layers,
+      width,
+      height,
+    })
+  }
+
+  pub fn from_str(image_data: &str, width: usize, height: usize) -> Result<SpaceImage> {
+    SpaceImage::from_digits(parse_pixels(Span::new(image_data).lines())?, width, height)
+  }
+
+  /// Each pixel is the color of the first layer where it isn't transparent.
+  fn decode(&self) -> Grid<Color> {
+    let mut decoded = Grid::filled(self.width, self.height, Color::Transparent);
+    for layer in &self.layers {
+      for (point, pixel) in layer.iter() {
+        if let Some(decoded @ Color::Transparent) = decoded.get_mut(point) {
+          *decoded = pixel.clone();
+        }
+      }
+    }
+    decoded
+  }
+
+  pub fn render(&self) -> String {
+    self.decode().render(Color::symbol)
+  }
+}
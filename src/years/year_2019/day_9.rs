@@ -0,0 +1,60 @@
+use advent_of_code::intcode::program::Program;
+
+use crate::{
+  solution::{Example, Part, Solution},
+  Result,
+};
+
+pub struct Day9;
+
+const TEST_MODE: isize = 1;
+const SENSOR_BOOST_MODE: isize = 2;
+
+impl Solution for Day9 {
+  type Input = Program;
+  type PartOne = isize;
+  type PartTwo = isize;
+
+  const EXAMPLES: &'static [Example] = &[
+    Example {
+      part: Part::One,
+      input: "1102,34915192,34915192,7,4,7,99,0",
+      answer: "1219070632396864",
+    },
+    Example {
+      part: Part::One,
+      input: "104,1125899906842624,99",
+      answer: "1125899906842624",
+    },
+  ];
+
+  fn parse(&self, input: &str) -> Result<Program> {
+    input.parse()
+  }
+
+  /// BOOST keycode
+  fn part_one(&self, program: &Program) -> Result<isize> {
+    run_boost(program, TEST_MODE)
+  }
+
+  /// Coordinates of the distress signal
+  fn part_two(&self, program: &Program) -> Result<isize> {
+    run_boost(program, SENSOR_BOOST_MODE)
+  }
+}
+
+fn run_boost(program: &Program, mode: isize) -> Result<isize> {
+  let output = program.clone().run(Some(vec![mode]))?;
+  match output.as_slice() {
+    [value] => Ok(*value),
+    [] => Err("No output from BOOST program".into()),
+    // Test mode outputs the opcodes that are not working correctly instead
+    _ => Err(
+      format!(
+        "BOOST program reported malfunctioning opcodes: {:?}",
+        output
+      )
+      .into(),
+    ),
+  }
+}
@@ -0,0 +1,121 @@
+use std::ops::RangeInclusive;
+
+use crate::solution::DynSolution;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+pub const DAYS: RangeInclusive<u8> = 1..=10;
+
+/// The solution for a day, `None` if the day isn't solved.
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+  match day {
+    1 => Some(&day_1::Day1),
+    2 => Some(&day_2::Day2),
+    3 => Some(&day_3::Day3),
+    4 => Some(&day_4::Day4),
+    5 => Some(&day_5::Day5),
+    6 => Some(&day_6::Day6),
+    7 => Some(&day_7::Day7),
+    8 => Some(&day_8::Day8),
+    9 => Some(&day_9::Day9),
+    10 => Some(&day_10::Day10),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::verify::{check_example, Outcome};
+
+  /// A test per day running each of its published examples.
+  macro_rules! example_tests {
+    ($($name:ident: $day:expr,)*) => {
+      $(
+        #[test]
+        fn $name() {
+          let solution = solution($day).unwrap();
+          for (i, example) in solution.examples().iter().enumerate() {
+            assert_eq!(
+              check_example(solution, example),
+              Outcome::Pass,
+              "part {} example {}",
+              example.part.number(),
+              i + 1
+            );
+          }
+        }
+      )*
+    };
+  }
+
+  example_tests! {
+    day_1_examples: 1,
+    day_2_examples: 2,
+    day_3_examples: 3,
+    day_4_examples: 4,
+    day_5_examples: 5,
+    day_6_examples: 6,
+    day_7_examples: 7,
+    day_8_examples: 8,
+    day_9_examples: 9,
+    day_10_examples: 10,
+  }
+
+  #[test]
+  fn covers_every_day() {
+    assert!(DAYS.clone().all(|day| solution(day).is_some()));
+  }
+
+  #[test]
+  fn reports_where_input_is_malformed() {
+    let cases = [
+      (
+        1,
+        "12\n1x4",
+        "line 2, column 1: invalid digit found in string",
+      ),
+      (
+        2,
+        "1,0,x",
+        "line 1, column 5: invalid digit found in string",
+      ),
+      (3, "R8,U5\nU7,X6", "line 2, column 4: Invalid direction: X"),
+      (
+        3,
+        "R8,,U5\nU7",
+        "line 1, column 4: Expected a letter, found nothing",
+      ),
+      (
+        4,
+        "100-2o0",
+        "line 1, column 5: invalid digit found in string",
+      ),
+      (
+        6,
+        "COM)B\nB)C)D",
+        "line 2, column 4: Expected a single `)` in `B)C)D`",
+      ),
+      (8, "2x2\n0123", "line 2, column 4: Invalid pixel: 3"),
+      (10, ".#\n#?", "line 2, column 2: Invalid cell: ?"),
+    ];
+    for (day, input, expected) in cases {
+      let error = solution(day).unwrap().parse(input).err();
+      assert_eq!(
+        error.map(|error| error.to_string()).as_deref(),
+        Some(expected),
+        "day {}",
+        day
+      );
+    }
+  }
+}